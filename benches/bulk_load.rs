@@ -0,0 +1,99 @@
+use lookups::{HashLookup, IndexLookup, LkupVec, Lookup};
+
+fn main() {
+    divan::main();
+}
+
+const SIZES: &[usize] = &[1_000, 100_000];
+
+// the count of Items for one `extend` call
+const EXTEND_SIZE: usize = 10;
+
+#[derive(Clone)]
+struct Person {
+    id: usize,
+    name: String,
+}
+
+fn persons(size: usize) -> Vec<Person> {
+    (0..size)
+        .map(|id| Person {
+            id,
+            name: format!("Name-{}", id % 100),
+        })
+        .collect()
+}
+
+#[divan::bench_group]
+mod index_unique {
+    use super::*;
+
+    #[divan::bench(args = SIZES)]
+    fn push(bencher: divan::Bencher, size: usize) {
+        bencher.with_inputs(|| persons(size)).bench_values(|items| {
+            let mut v = LkupVec::new(IndexLookup::with_unique_key(), |p: &Person| p.id);
+            items.into_iter().for_each(|p| {
+                v.push(p);
+            });
+            v
+        });
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn load(bencher: divan::Bencher, size: usize) {
+        bencher.with_inputs(|| persons(size)).bench_values(|items| {
+            let mut v = LkupVec::new(IndexLookup::with_unique_key(), |p: &Person| p.id);
+            v.load(items);
+            v
+        });
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn extend_small(bencher: divan::Bencher, size: usize) {
+        bencher.with_inputs(|| persons(size)).bench_values(|items| {
+            let mut v = LkupVec::new(IndexLookup::with_unique_key(), |p: &Person| p.id);
+            let mut items = items.into_iter();
+            while items.len() > 0 {
+                v.extend(items.by_ref().take(EXTEND_SIZE));
+            }
+            v
+        });
+    }
+}
+
+#[divan::bench_group]
+mod hash_multi {
+    use super::*;
+
+    #[divan::bench(args = SIZES)]
+    fn push(bencher: divan::Bencher, size: usize) {
+        bencher.with_inputs(|| persons(size)).bench_values(|items| {
+            let mut v = LkupVec::new(HashLookup::with_multi_keys(), |p: &Person| p.name.clone());
+            items.into_iter().for_each(|p| {
+                v.push(p);
+            });
+            v
+        });
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn load(bencher: divan::Bencher, size: usize) {
+        bencher.with_inputs(|| persons(size)).bench_values(|items| {
+            let mut v = LkupVec::new(HashLookup::with_multi_keys(), |p: &Person| p.name.clone());
+            v.load(items);
+            v
+        });
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn extend_small(bencher: divan::Bencher, size: usize) {
+        bencher.with_inputs(|| persons(size)).bench_values(|items| {
+            let mut v = LkupVec::new(HashLookup::with_multi_keys(), |p: &Person| p.name.clone());
+            let mut items = items.into_iter();
+            while items.len() > 0 {
+                v.extend(items.by_ref().take(EXTEND_SIZE));
+            }
+            v
+        });
+    }
+}
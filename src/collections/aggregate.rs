@@ -0,0 +1,126 @@
+//! The `aggregate` module contains the `Aggregator`s, which are used by the `aggregate_by_lkup_key` functions,
+//! e.g. [`crate::collections::list::ro::LkupList::aggregate_by_lkup_key`].
+//!
+//! # Example
+//!
+//! ```
+//! use lookups::{collections::{aggregate::Sum, list::ro::LkupList}, IndexLookup, Lookup};
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Order {
+//!     customer: usize,
+//!     price: u32,
+//! }
+//!
+//! let orders = [
+//!     Order{customer: 1, price: 10},
+//!     Order{customer: 2, price: 5},
+//!     Order{customer: 1, price: 7},
+//! ];
+//!
+//! let list = LkupList::new(IndexLookup::with_multi_keys(), |o| o.customer, orders);
+//!
+//! assert_eq!(2, list.count_by_lkup_key(1));
+//! assert_eq!(
+//!     vec![(&1, 17), (&2, 5)],
+//!     list.aggregate_by_lkup_key(|o| o.price, Sum).collect::<Vec<_>>()
+//! );
+//! ```
+//!
+
+/// An `Aggregator` combines all values (e.g. the price of the `Item`s) for one `Key` to one result.
+pub trait Aggregator<T> {
+    type Output;
+
+    /// Combines all `values` to one result.
+    fn aggregate<It>(&self, values: It) -> Self::Output
+    where
+        It: Iterator<Item = T>;
+}
+
+/// The sum of all values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sum;
+
+impl<T> Aggregator<T> for Sum
+where
+    T: std::iter::Sum<T>,
+{
+    type Output = T;
+
+    fn aggregate<It>(&self, values: It) -> Self::Output
+    where
+        It: Iterator<Item = T>,
+    {
+        values.sum()
+    }
+}
+
+/// The count of all values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Count;
+
+impl<T> Aggregator<T> for Count {
+    type Output = usize;
+
+    fn aggregate<It>(&self, values: It) -> Self::Output
+    where
+        It: Iterator<Item = T>,
+    {
+        values.count()
+    }
+}
+
+/// The smallest value, or `None` if there are no values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Min;
+
+impl<T> Aggregator<T> for Min
+where
+    T: Ord,
+{
+    type Output = Option<T>;
+
+    fn aggregate<It>(&self, values: It) -> Self::Output
+    where
+        It: Iterator<Item = T>,
+    {
+        values.min()
+    }
+}
+
+/// The greatest value, or `None` if there are no values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Max;
+
+impl<T> Aggregator<T> for Max
+where
+    T: Ord,
+{
+    type Output = Option<T>;
+
+    fn aggregate<It>(&self, values: It) -> Self::Output
+    where
+        It: Iterator<Item = T>,
+    {
+        values.max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregators() {
+        let values = [3, 1, 7];
+
+        assert_eq!(11, Sum.aggregate(values.into_iter()));
+        assert_eq!(3, Aggregator::<i32>::aggregate(&Count, values.into_iter()));
+        assert_eq!(Some(1), Min.aggregate(values.into_iter()));
+        assert_eq!(Some(7), Max.aggregate(values.into_iter()));
+
+        assert_eq!(0, Sum.aggregate(std::iter::empty::<i32>()));
+        assert_eq!(None, Max.aggregate(std::iter::empty::<i32>()));
+    }
+}
@@ -0,0 +1,232 @@
+//! The `join` module contains functions for joining two collections on a shared `Key`.
+//!
+//! The `left` side is iterated, for every `left` `Item` the `Key` is created and
+//! the matching `right` `Item`s are found by the `Lookup` from the `right` side (no nested loops).
+//!
+//! # Example
+//!
+//! ```
+//! use lookups::{collections::join::{inner_join, left_join}, HashLookup, IndexLookup, LkupHashMap, LkupVec, Lookup};
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Order { id: usize, customer_id: usize }
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Customer { id: usize, name: &'static str }
+//!
+//! let orders = LkupVec::from_iter(
+//!     HashLookup::with_multi_keys(),
+//!     |o: &Order| o.customer_id,
+//!     [Order{id: 1, customer_id: 7}, Order{id: 2, customer_id: 3}],
+//! );
+//!
+//! let customers = LkupHashMap::from_iter(
+//!     IndexLookup::with_unique_key(),
+//!     |c: &Customer| c.id,
+//!     [("Paul", Customer{id: 7, name: "Paul"})],
+//! );
+//!
+//! assert_eq!(
+//!     vec![(&Order{id: 1, customer_id: 7}, &Customer{id: 7, name: "Paul"})],
+//!     inner_join(orders.iter(), &customers, |o| o.customer_id).collect::<Vec<_>>()
+//! );
+//!
+//! assert_eq!(
+//!     vec![
+//!         (&Order{id: 1, customer_id: 7}, Some(&Customer{id: 7, name: "Paul"})),
+//!         (&Order{id: 2, customer_id: 3}, None),
+//!     ],
+//!     left_join(orders.iter(), &customers, |o| o.customer_id).collect::<Vec<_>>()
+//! );
+//! ```
+//!
+use crate::{
+    collections::{
+        list::{ro::LkupList, rw::LkupVec},
+        map::{ro::LkupHashMap, rw::LkupHashMap as RwLkupHashMap},
+        View,
+    },
+    lookup::store::{Retriever, Store},
+};
+use std::{
+    hash::{BuildHasher, Hash},
+    ops::Index,
+};
+
+/// `ItemRetriever` returns all `Item`s for a given `Key` (e.g. a collection or a `View`).
+pub trait ItemRetriever<'a, Q> {
+    type Item: ?Sized + 'a;
+
+    /// Returns all `Item`s for the given `key`.
+    fn items_by_key(&'a self, key: Q) -> impl Iterator<Item = &'a Self::Item>;
+}
+
+/// Returns all pairs of `left` and `right` `Item`s with the same `Key`.
+///
+/// The `key` function creates the `Key` for the `right` side from a `left` `Item`.
+pub fn inner_join<'l, 'r, T, It, R, F, Q>(
+    left: It,
+    right: &'r R,
+    key: F,
+) -> impl Iterator<Item = (&'l T, &'r R::Item)>
+where
+    It: IntoIterator<Item = &'l T>,
+    T: ?Sized + 'l,
+    R: ItemRetriever<'r, Q>,
+    F: Fn(&'l T) -> Q,
+{
+    left.into_iter()
+        .flat_map(move |l| right.items_by_key(key(l)).map(move |r| (l, r)))
+}
+
+/// Returns all pairs of `left` and `right` `Item`s with the same `Key`.
+/// If there is no `right` `Item` for a `left` `Item`, the pair is: `(left, None)`.
+///
+/// The `key` function creates the `Key` for the `right` side from a `left` `Item`.
+pub fn left_join<'l, 'r, T, It, R, F, Q>(
+    left: It,
+    right: &'r R,
+    key: F,
+) -> impl Iterator<Item = (&'l T, Option<&'r R::Item>)>
+where
+    It: IntoIterator<Item = &'l T>,
+    T: ?Sized + 'l,
+    R: ItemRetriever<'r, Q>,
+    F: Fn(&'l T) -> Q,
+{
+    left.into_iter().flat_map(move |l| {
+        let mut rights = right.items_by_key(key(l)).peekable();
+        let no_match = rights.peek().is_none().then_some((l, None));
+        no_match
+            .into_iter()
+            .chain(rights.map(move |r| (l, Some(r))))
+    })
+}
+
+impl<'a, S, I, Q> ItemRetriever<'a, Q> for LkupList<S, I>
+where
+    S: Store<Pos = usize> + Retriever<Q, Pos = usize>,
+    I: Index<usize> + 'a,
+    S: 'a,
+{
+    type Item = I::Output;
+
+    fn items_by_key(&'a self, key: Q) -> impl Iterator<Item = &'a Self::Item> {
+        self.get_by_lkup_key(key)
+    }
+}
+
+impl<'a, S, F, I, Q> ItemRetriever<'a, Q> for LkupVec<S, F, I>
+where
+    S: Store<Pos = usize> + Retriever<Q, Pos = usize>,
+    I: 'a,
+    S: 'a,
+    F: 'a,
+{
+    type Item = I;
+
+    fn items_by_key(&'a self, key: Q) -> impl Iterator<Item = &'a Self::Item> {
+        self.get_by_lkup_key(key)
+    }
+}
+
+impl<'a, S, K, V, H, Q> ItemRetriever<'a, Q> for LkupHashMap<S, K, V, H>
+where
+    S: Store<Pos = K> + Retriever<Q, Pos = K>,
+    K: Hash + Eq,
+    H: BuildHasher,
+    S: 'a,
+    K: 'a,
+    V: 'a,
+    H: 'a,
+{
+    type Item = V;
+
+    fn items_by_key(&'a self, key: Q) -> impl Iterator<Item = &'a Self::Item> {
+        self.get_by_lkup_key(key)
+    }
+}
+
+impl<'a, S, F, K, V, H, Q> ItemRetriever<'a, Q> for RwLkupHashMap<S, F, K, V, H>
+where
+    S: Store<Pos = K> + Retriever<Q, Pos = K>,
+    K: Hash + Eq,
+    H: BuildHasher,
+    S: 'a,
+    F: 'a,
+    K: 'a,
+    V: 'a,
+    H: 'a,
+{
+    type Item = V;
+
+    fn items_by_key(&'a self, key: Q) -> impl Iterator<Item = &'a Self::Item> {
+        self.get_by_lkup_key(key)
+    }
+}
+
+impl<'a, R, I, Q> ItemRetriever<'a, Q> for View<R, I>
+where
+    R: Retriever<Q> + 'a,
+    I: Index<&'a R::Pos> + 'a,
+    Q: 'a,
+{
+    type Item = I::Output;
+
+    fn items_by_key(&'a self, key: Q) -> impl Iterator<Item = &'a Self::Item> {
+        self.get_by_key(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashLookup, IndexLookup, Lookup};
+
+    #[derive(Debug, PartialEq)]
+    struct Order(usize, &'static str);
+
+    #[derive(Debug, PartialEq)]
+    struct Tag(&'static str, &'static str);
+
+    #[test]
+    fn join_multi_keys_and_views() {
+        let orders = LkupList::new(
+            IndexLookup::with_unique_key(),
+            |o: &Order| o.0,
+            vec![Order(1, "a"), Order(2, "b"), Order(3, "c")],
+        );
+        let tags = LkupList::new(
+            HashLookup::with_multi_keys(),
+            |t: &Tag| t.0.to_string(),
+            vec![Tag("a", "new"), Tag("a", "paid"), Tag("c", "new")],
+        );
+
+        assert_eq!(
+            vec![
+                (&Order(1, "a"), &Tag("a", "new")),
+                (&Order(1, "a"), &Tag("a", "paid")),
+                (&Order(3, "c"), &Tag("c", "new")),
+            ],
+            inner_join(orders.iter(), &tags, |o| o.1).collect::<Vec<_>>()
+        );
+
+        // join a view (left) with a view (right)
+        let left = orders.create_lkup_view([2, 3]);
+        let right = tags.create_lkup_view([String::from("a"), String::from("b")]);
+        assert_eq!(
+            vec![(&Order(2, "b"), None), (&Order(3, "c"), None)],
+            left_join(left.items(), &right, |o| o.1).collect::<Vec<_>>()
+        );
+
+        // the other direction, the right side is an unique key store
+        assert_eq!(
+            vec![(&Tag("c", "new"), &Order(3, "c"))],
+            inner_join(tags.iter().skip(2), &orders, |t| match t.0 {
+                "c" => 3,
+                _ => 0,
+            })
+            .collect::<Vec<_>>()
+        );
+    }
+}
@@ -4,10 +4,22 @@
 pub mod ro;
 pub mod rw;
 
-use std::ops::Index;
+use std::ops::{Deref, Index};
 
+/// A borrowed list of `Item`s (e.g. a `Vec`), which can be used as `Item`s for a [`ro::LkupList`].
 pub struct ListIndex<'a, I>(&'a I);
 
+impl<I> Index<usize> for ListIndex<'_, I>
+where
+    I: Index<usize>,
+{
+    type Output = I::Output;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.0.index(index)
+    }
+}
+
 impl<I> Index<&usize> for ListIndex<'_, I>
 where
     I: Index<usize>,
@@ -18,3 +30,20 @@ where
         self.0.index(*index)
     }
 }
+
+impl<I, T> AsRef<[T]> for ListIndex<'_, I>
+where
+    I: AsRef<[T]>,
+{
+    fn as_ref(&self) -> &[T] {
+        self.0.as_ref()
+    }
+}
+
+impl<I> Deref for ListIndex<'_, I> {
+    type Target = I;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
@@ -1,8 +1,18 @@
 //! `Read only` implementations for lookup collections [`LkupList`] like `Vec`, `Slice`, ...
 //!
-use crate::collections::{list::ListIndex, View};
-use crate::lookup::store::{position::KeyPosition, Lookup, Retriever, Store, ViewCreator};
-use std::ops::{Deref, Index};
+use crate::collections::{aggregate::Aggregator, list::ListIndex, View};
+use crate::lookup::{
+    frozen::FrozenHashStore,
+    hash::HashStore,
+    store::{
+        self, owned::OwnedView, position::KeyPosition, Groups, Lookup, Positions, Retriever, Store,
+        ViewCreator,
+    },
+};
+use std::{
+    hash::{BuildHasher, Hash},
+    ops::{Deref, Index},
+};
 
 /// [`LkupList`] is a read only lookup extenstion for a [`std::vec::Vec`].
 ///
@@ -42,6 +52,8 @@ use std::ops::{Deref, Index};
 pub struct LkupList<S, I> {
     pub(crate) store: S,
     pub(crate) items: I,
+    // is incremented by every modification of the (rw) collection, to detect a stale `OwnedView`
+    pub(crate) generation: usize,
 }
 
 impl<S, I> LkupList<S, I>
@@ -56,7 +68,40 @@ where
         I: AsRef<[T]>,
     {
         let store = lookup.new_list_store(&field, items.as_ref().iter());
-        Self { store, items }
+        Self {
+            store,
+            items,
+            generation: 0,
+        }
+    }
+
+    /// Create a new [`LkupList`], the `Store` is created in parallel (feature = "rayon").
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, HashLookup, Lookup};
+    ///
+    /// let v = LkupList::par_new(HashLookup::with_multi_keys(), |n: &usize| n % 10, (0..100).collect::<Vec<_>>());
+    ///
+    /// assert_eq!(vec![&3, &13], v.get_by_lkup_key(&3).take(2).collect::<Vec<_>>());
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_new<L, P, F, T>(lookup: L, field: F, items: I) -> Self
+    where
+        L: Lookup<S, P> + Sync,
+        P: KeyPosition<Pos = usize>,
+        F: Fn(&T) -> S::Key + Sync,
+        I: AsRef<[T]>,
+        T: Sync,
+        S: store::MergeStore + Send,
+    {
+        let store = lookup.par_new_list_store(&field, items.as_ref());
+        Self {
+            store,
+            items,
+            generation: 0,
+        }
     }
 
     pub fn contains_lkup_key<Q>(&self, key: Q) -> bool
@@ -83,6 +128,163 @@ where
         self.store.pos_by_many_keys(keys).map(|p| &self.items[*p])
     }
 
+    /// Like [`LkupList::get_by_many_lkup_keys`], but every `Item` is returned only once
+    /// (in the order of the first occurrence).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, HashLookup, Lookup};
+    ///
+    /// let list = LkupList::new(HashLookup::with_multi_keys(), |s: &&str| s.len(), ["ab", "c", "de"]);
+    ///
+    /// assert_eq!(
+    ///     vec![&"c", &"ab", &"de"],
+    ///     list.get_by_unique_many_lkup_keys([&1, &2, &1]).collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn get_by_unique_many_lkup_keys<It, Q>(&self, keys: It) -> impl Iterator<Item = &I::Output>
+    where
+        S: Retriever<Q, Pos = usize>,
+        I: Index<usize>,
+        It: IntoIterator<Item = Q>,
+    {
+        self.store
+            .pos_by_many_keys_dedup(keys)
+            .map(|p| &self.items[*p])
+    }
+
+    /// Like [`LkupList::get_by_unique_many_lkup_keys`], but the `Item`s are ordered by the position in the list.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, HashLookup, Lookup};
+    ///
+    /// let list = LkupList::new(HashLookup::with_multi_keys(), |s: &&str| s.len(), ["ab", "c", "de"]);
+    ///
+    /// assert_eq!(
+    ///     vec![&"ab", &"c", &"de"],
+    ///     list.get_by_unique_many_lkup_keys_sorted([&1, &2, &1]).collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn get_by_unique_many_lkup_keys_sorted<It, Q>(
+        &self,
+        keys: It,
+    ) -> impl Iterator<Item = &I::Output>
+    where
+        S: Retriever<Q, Pos = usize>,
+        I: Index<usize>,
+        It: IntoIterator<Item = Q>,
+    {
+        self.store
+            .pos_by_many_keys_sorted(keys)
+            .map(|p| &self.items[*p])
+    }
+
+    /// Returns all `Key`s with the associated `Item`s, in one pass over the `Store`
+    /// (ordered by the `Key`, if the `Store` is ordered, e.g. `IndexStore`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, IndexLookup, Lookup};
+    ///
+    /// let list = LkupList::new(IndexLookup::with_multi_keys(), |s: &&str| s.len(), ["ab", "c", "de"]);
+    ///
+    /// let groups = list
+    ///     .group_by_lkup_key()
+    ///     .map(|(len, items)| (*len, items.collect::<Vec<_>>()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![(1, vec![&"c"]), (2, vec![&"ab", &"de"])], groups);
+    /// ```
+    pub fn group_by_lkup_key(
+        &self,
+    ) -> impl Iterator<Item = (&<S as Groups>::Key, impl Iterator<Item = &I::Output>)>
+    where
+        S: Groups<Pos = usize>,
+        I: Index<usize>,
+    {
+        self.store
+            .groups()
+            .map(|(key, pos)| (key, pos.iter().map(|p| &self.items[*p])))
+    }
+
+    /// Returns the count of `Item`s for the given `Key` (without iterating the `Item`s).
+    pub fn count_by_lkup_key<Q>(&self, key: Q) -> usize
+    where
+        S: Retriever<Q>,
+    {
+        self.store.pos_by_key(key).len()
+    }
+
+    /// Returns the count of `Item`s for all given `Key`s.
+    pub fn count_by_many_lkup_keys<It, Q>(&self, keys: It) -> usize
+    where
+        S: Retriever<Q>,
+        It: IntoIterator<Item = Q>,
+    {
+        self.store.count_by_many_keys(keys)
+    }
+
+    /// Returns the count of the given `Key`s, which exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, IndexLookup, Lookup};
+    ///
+    /// let c = LkupList::new(IndexLookup::with_multi_keys(), |n: &usize| *n, [1, 3, 1]);
+    ///
+    /// assert_eq!(2, c.count_existing_lkup_keys([1, 3, 7]));
+    /// assert_eq!(vec![7, 9], c.missing_lkup_keys([1, 7, 9]).collect::<Vec<_>>());
+    /// assert_eq!(3, c.count_by_many_lkup_keys([1, 3, 7]));
+    /// ```
+    pub fn count_existing_lkup_keys<It, Q>(&self, keys: It) -> usize
+    where
+        S: Retriever<Q>,
+        It: IntoIterator<Item = Q>,
+    {
+        self.store.count_existing_keys(keys)
+    }
+
+    /// Returns all given `Key`s, which not exist.
+    pub fn missing_lkup_keys<'a, It, Q>(&'a self, keys: It) -> impl Iterator<Item = Q> + 'a
+    where
+        S: Retriever<Q>,
+        It: IntoIterator<Item = Q> + 'a,
+        Q: Clone + 'a,
+    {
+        self.store.missing_keys(keys)
+    }
+
+    /// Returns all `Key`s with the count of the associated `Item`s.
+    pub fn histogram(&self) -> impl Iterator<Item = (&<S as Groups>::Key, usize)>
+    where
+        S: Groups,
+    {
+        self.store.groups().map(|(key, pos)| (key, pos.len()))
+    }
+
+    /// Returns all `Key`s with the aggregated `value`s of the associated `Item`s
+    /// (see: [`crate::collections::aggregate`]).
+    pub fn aggregate_by_lkup_key<'a, F, T, A>(
+        &'a self,
+        value: F,
+        aggregator: A,
+    ) -> impl Iterator<Item = (&'a <S as Groups>::Key, A::Output)> + 'a
+    where
+        S: Groups<Pos = usize>,
+        I: Index<usize>,
+        F: Fn(&'a I::Output) -> T + 'a,
+        A: Aggregator<T> + 'a,
+    {
+        self.store.groups().map(move |(key, pos)| {
+            let values = pos.iter().map(|p| value(&self.items[*p]));
+            (key, aggregator.aggregate(values))
+        })
+    }
+
     pub fn lkup_ext(&self) -> &S::Target
     where
         S: Deref,
@@ -98,6 +300,160 @@ where
         let view = self.store.create_view(keys);
         View::new(view, ListIndex(&self.items))
     }
+
+    /// Create a `View` with all `Key`s of the collection (no `Key`s are copied).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, IndexLookup, Lookup};
+    ///
+    /// let list = LkupList::new(IndexLookup::with_multi_keys(), |s: &&str| s.len(), ["ab", "c", "de"]);
+    ///
+    /// let view = list.create_full_view();
+    /// assert_eq!(3, view.items().count());
+    /// assert_eq!(vec![&"c", &"ab"], view.page(0, 2).collect::<Vec<_>>());
+    /// ```
+    pub fn create_full_view(&self) -> View<&S, ListIndex<'_, I>> {
+        View::new(store::View::new(&self.store), ListIndex(&self.items))
+    }
+
+    /// Returns all `Item`s in the order of the `Store`
+    /// (ordered by the `Key`, if the `Store` is ordered, e.g. `IndexStore`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, IndexLookup, Lookup};
+    ///
+    /// let list = LkupList::new(IndexLookup::with_unique_key(), |n: &usize| *n, [5, 1, 3]);
+    ///
+    /// assert_eq!(vec![&1, &3, &5], list.all_items_by_lkup_order().collect::<Vec<_>>());
+    /// ```
+    pub fn all_items_by_lkup_order(&self) -> impl Iterator<Item = &I::Output>
+    where
+        S: Positions<Pos = usize>,
+        I: Index<usize>,
+    {
+        self.store.positions().map(|p| &self.items[*p])
+    }
+
+    /// Create an [`OwnedView`], which is not borrowing the collection.
+    /// The `View` can be used with [`LkupList::with_owned_view`] and refreshed,
+    /// after the collection was modified, with [`LkupList::refresh_owned_view`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, HashLookup, Lookup};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Car(usize, String);
+    ///
+    /// let cars = [Car(5, "BMW".into()), Car(1, "Audi".into()), Car(2, "BMW".into())];
+    /// let list = LkupList::new(HashLookup::with_multi_keys(), |c| c.1.clone(), cars);
+    ///
+    /// let bmws = list.create_owned_lkup_view([String::from("BMW")]);
+    ///
+    /// assert_eq!(
+    ///     vec![&Car(5, "BMW".into()), &Car(2, "BMW".into())],
+    ///     list.with_owned_view(&bmws).unwrap().items().collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn create_owned_lkup_view<'a, K, It>(&'a self, keys: It) -> OwnedView<K, S>
+    where
+        It: IntoIterator<Item = K>,
+        S: Store<Key = K> + ViewCreator<'a, Key = K>,
+        <S as ViewCreator<'a>>::Retriever: Groups<Key = K, Pos = usize>,
+        K: Clone,
+    {
+        let mut view = OwnedView::new(&self.store, keys);
+        view.generation = self.generation;
+        view
+    }
+
+    /// Refresh the given [`OwnedView`] (see: [`LkupList::create_owned_lkup_view`]).
+    pub fn refresh_owned_view<'a, K>(&'a self, view: &mut OwnedView<K, S>)
+    where
+        S: Store<Key = K> + ViewCreator<'a, Key = K>,
+        <S as ViewCreator<'a>>::Retriever: Groups<Key = K, Pos = usize>,
+        K: Clone,
+    {
+        view.refresh(&self.store);
+        view.generation = self.generation;
+    }
+
+    /// Combine the given [`OwnedView`] with the `Item`s of this collection to a [`View`].
+    ///
+    /// Returns `None`, if the collection was modified after the [`OwnedView`] was created or refreshed,
+    /// because the `Position`s of the `View` can be invalid (see: [`LkupList::refresh_owned_view`]).
+    pub fn with_owned_view<'a, K>(
+        &'a self,
+        view: &'a OwnedView<K, S>,
+    ) -> Option<View<&'a OwnedView<K, S>, ListIndex<'a, I>>> {
+        (view.generation == self.generation)
+            .then(|| View::new(store::View::new(view), ListIndex(&self.items)))
+    }
+}
+
+impl<S, I> LkupList<S, I> {
+    /// Create a new [`LkupList`] with an other `Lookup` and `field`, where the `Item`s are borrowed (not cloned).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, HashLookup, IndexLookup, Lookup};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Car(usize, String);
+    ///
+    /// let cars = [Car(5, "BMW".into()), Car(1, "Audi".into()), Car(2, "BMW".into())];
+    /// let list = LkupList::new(IndexLookup::with_unique_key(), |c| c.0, cars);
+    ///
+    /// // a temporary Lookup for the name
+    /// let by_name = list.reindex(HashLookup::with_multi_keys(), |c: &Car| c.1.clone());
+    /// assert_eq!(
+    ///     vec![&Car(5, "BMW".into()), &Car(2, "BMW".into())],
+    ///     by_name.get_by_lkup_key("BMW").collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn reindex<S2, L, P, F, T>(&self, lookup: L, field: F) -> LkupList<S2, ListIndex<'_, I>>
+    where
+        S2: Store<Pos = usize>,
+        L: Lookup<S2, P>,
+        P: KeyPosition<Pos = usize>,
+        F: Fn(&T) -> S2::Key,
+        I: AsRef<[T]>,
+    {
+        LkupList::new(lookup, field, ListIndex(&self.items))
+    }
+}
+
+impl<K, P, H, I> LkupList<HashStore<K, P, H>, I>
+where
+    K: Hash + Eq,
+    P: KeyPosition,
+    H: BuildHasher + Clone,
+{
+    /// Convert the `HashStore` into a [`FrozenHashStore`], which needs less memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, HashLookup, Lookup};
+    ///
+    /// let list = LkupList::new(HashLookup::with_unique_key(), |s: &&str| s.to_string(), ["a", "b"]);
+    /// let list = list.freeze();
+    ///
+    /// assert!(list.contains_lkup_key("b"));
+    /// ```
+    pub fn freeze(self) -> LkupList<FrozenHashStore<K, P, H>, I> {
+        LkupList {
+            store: self.store.freeze(),
+            items: self.items,
+            generation: self.generation,
+        }
+    }
 }
 
 impl<S, I> Deref for LkupList<S, I> {
@@ -210,6 +566,33 @@ mod tests {
         assert_eq!(vec![&String::from("Audi")], view.keys().collect::<Vec<_>>());
     }
 
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_new() {
+        let items = (0..10_000u16)
+            .map(|n| Car(n % 1_000, format!("Car-{}", n % 777)))
+            .collect::<Vec<_>>();
+
+        let idx = LkupList::new(IndexLookup::with_multi_keys(), Car::id, &items);
+        let par_idx = LkupList::par_new(IndexLookup::with_multi_keys(), Car::id, &items);
+        for key in 0..1_000 {
+            assert_eq!(idx.store.pos_by_key(key), par_idx.store.pos_by_key(key));
+        }
+
+        let hash = LkupList::new(HashLookup::with_multi_keys(), Car::name, &items);
+        let par_hash = LkupList::par_new(HashLookup::with_multi_keys(), Car::name, &items);
+        for key in hash.lkup_ext().keys() {
+            assert_eq!(hash.store.pos_by_key(key), par_hash.store.pos_by_key(key));
+        }
+        assert_eq!(
+            hash.lkup_ext().keys().count(),
+            par_hash.lkup_ext().keys().count()
+        );
+
+        let empty = LkupList::par_new(HashLookup::with_unique_key(), Car::name, Vec::new());
+        assert!(!empty.contains_lkup_key("Car-1"));
+    }
+
     #[test]
     fn lkuplist_with_key_and_clone() {
         let items = [Car(99, "Audi".into()), Car(1, "BMW".into())];
@@ -230,4 +613,31 @@ mod tests {
             v.get_by_many_lkup_keys([99, 1]).collect::<Vec<_>>()
         );
     }
+
+    #[test]
+    fn reindex() {
+        let cars = vec![
+            Car(99, "Audi".into()),
+            Car(1, "BMW".into()),
+            Car(7, "Audi".into()),
+        ];
+        let v = LkupList::new(IndexLookup::with_unique_key(), |c: &Car| c.0, cars);
+
+        let by_name = v.reindex(HashLookup::with_multi_keys(), |c: &Car| c.1.clone());
+        assert!(by_name.contains_lkup_key("BMW"));
+        assert_eq!(
+            vec![&Car(99, "Audi".into()), &Car(7, "Audi".into())],
+            by_name.get_by_lkup_key("Audi").collect::<Vec<_>>()
+        );
+        assert_eq!(2, by_name.lkup_ext().keys().count());
+
+        let view = by_name.create_lkup_view([String::from("BMW")]);
+        assert_eq!(
+            vec![&Car(1, "BMW".into())],
+            view.items().collect::<Vec<_>>()
+        );
+
+        // the Items are borrowed
+        assert!(std::ptr::eq(&v[0], &by_name[0]));
+    }
 }
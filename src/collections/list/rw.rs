@@ -2,10 +2,22 @@
 //!
 
 use crate::{
-    collections::list::ro,
-    lookup::store::{position::KeyPosition, Lookup, Retriever, Store},
+    collections::{
+        list::{ro, ListIndex},
+        live::{LiveStore, LiveViews, ViewHandle},
+        View,
+    },
+    lookup::{
+        field::Field,
+        store::{self, position::KeyPosition, Lookup, Retriever, Store},
+    },
+};
+use std::{
+    cmp::Ordering,
+    fmt::Debug,
+    hash::Hash,
+    ops::{Deref, RangeBounds},
 };
-use std::{fmt::Debug, ops::Deref};
 
 /// [`LkupVec`] is a [`std::vec::Vec`] with one `Lookup`.
 ///
@@ -41,17 +53,20 @@ use std::{fmt::Debug, ops::Deref};
 /// );
 /// ```
 ///
-
+/// The `field` can be a function, which returns an owned `Key` (like above) or
+/// a [`crate::Borrowed`] `Field`, which returns a borrowed `Key` (e.g. `&str`).
+///
 #[derive(Debug, Clone)]
 pub struct LkupVec<S, F, I> {
     field: F,
     inner: ro::LkupList<S, Vec<I>>,
+    views: LiveViews<S, F, I>,
 }
 
 impl<S, F, I> LkupVec<S, F, I>
 where
     S: Store<Pos = usize>,
-    F: Fn(&I) -> S::Key,
+    F: Field<S, I>,
 {
     pub fn new<L, P>(lookup: L, field: F) -> Self
     where
@@ -59,8 +74,143 @@ where
         P: KeyPosition<Pos = usize>,
     {
         Self {
-            inner: ro::LkupList::new(lookup, &field, Vec::new()),
+            inner: ro::LkupList {
+                store: lookup.new_store(0),
+                items: Vec::new(),
+                generation: 0,
+            },
             field,
+            views: LiveViews::default(),
+        }
+    }
+
+    /// Create a new [`LkupVec`] from the given `Item`s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{LkupVec, IndexLookup, Lookup};
+    ///
+    /// #[derive(PartialEq, Debug)]
+    /// struct Car(usize, String);
+    ///
+    /// let cars = [Car(5, "BMW".into()), Car(1, "Audi".into())];
+    /// let vec = LkupVec::from_iter(IndexLookup::with_unique_key(), |c: &Car| c.0, cars);
+    ///
+    /// assert!(vec.contains_lkup_key(1));
+    /// assert_eq!(vec![Car(5, "BMW".into()), Car(1, "Audi".into())], vec.into_inner());
+    /// ```
+    pub fn from_iter<L, P, It>(lookup: L, field: F, iter: It) -> Self
+    where
+        L: Lookup<S, P>,
+        P: KeyPosition<Pos = usize>,
+        It: IntoIterator<Item = I>,
+    {
+        let mut v = Self::new(lookup, field);
+        v.load(iter);
+        v
+    }
+}
+
+impl<S, F, I> LkupVec<S, F, I> {
+    /// Consumes the [`LkupVec`] and returns the inner [`std::vec::Vec`] with all `Item`s.
+    pub fn into_inner(self) -> Vec<I> {
+        self.inner.items
+    }
+
+    /// Consumes the [`LkupVec`] and returns a read only [`ro::LkupList`], the `Store` is reused.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{LkupVec, IndexLookup, Lookup};
+    ///
+    /// let mut vec = LkupVec::new(IndexLookup::with_unique_key(), |id: &usize| *id);
+    /// vec.push(3);
+    /// vec.push(1);
+    ///
+    /// let list = vec.into_ro();
+    /// assert!(list.contains_lkup_key(3));
+    ///
+    /// // and back, with the same field
+    /// let mut vec = list.into_rw(|id: &usize| *id);
+    /// vec.push(7);
+    /// assert!(vec.contains_lkup_key(7));
+    /// ```
+    pub fn into_ro(self) -> ro::LkupList<S, Vec<I>> {
+        self.inner
+    }
+
+    /// Consumes the [`LkupVec`] and returns a new [`LkupVec`] with the same `Item`s,
+    /// where the `Store` is created by the given `lookup` and `field`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{LkupVec, HashLookup, IndexLookup, Lookup};
+    ///
+    /// let vec = LkupVec::from_iter(IndexLookup::with_unique_key(), |id: &usize| *id, [3, 1]);
+    ///
+    /// // switch from an IndexLookup to a HashLookup with a String Key
+    /// let vec = vec.rebuild_store_with(HashLookup::with_unique_key(), |id: &usize| id.to_string());
+    /// assert!(vec.contains_lkup_key("3"));
+    /// ```
+    pub fn rebuild_store_with<S2, F2, L, P>(self, lookup: L, field: F2) -> LkupVec<S2, F2, I>
+    where
+        S2: Store<Pos = usize>,
+        F2: Field<S2, I>,
+        L: Lookup<S2, P>,
+        P: KeyPosition<Pos = usize>,
+    {
+        let items = self.inner.items;
+        let mut store = lookup.new_store(items.len());
+        field.insert_many(
+            &mut store,
+            items.iter().enumerate().map(|(idx, item)| (item, idx)),
+        );
+
+        LkupVec {
+            field,
+            inner: ro::LkupList {
+                store,
+                items,
+                generation: 0,
+            },
+            views: LiveViews::default(),
+        }
+    }
+
+    /// Returns the live `View` for the given `handle` (see: [`LkupVec::register_view`]),
+    /// or `None`, if the `View` was removed.
+    pub fn live_view(
+        &self,
+        handle: ViewHandle,
+    ) -> Option<View<&LiveStore<S>, ListIndex<'_, Vec<I>>>> {
+        self.views
+            .get(handle)
+            .map(|s| View::new(store::View::new(s), ListIndex(&self.inner.items)))
+    }
+
+    /// Removes the live `View` for the given `handle`.
+    /// Returns `false`, if the `View` was already removed.
+    pub fn unregister_view(&mut self, handle: ViewHandle) -> bool {
+        self.views.remove(handle)
+    }
+}
+
+impl<S, I> ro::LkupList<S, Vec<I>> {
+    /// Consumes the read only [`ro::LkupList`] and returns a [`LkupVec`], the `Store` is reused.
+    ///
+    /// The `field` must create the same `Key`s, which are used by creating the `Store`.
+    pub fn into_rw<F>(self, field: F) -> LkupVec<S, F, I>
+    where
+        S: Store<Pos = usize>,
+        F: Field<S, I>,
+    {
+        LkupVec {
+            field,
+            inner: self,
+            views: LiveViews::default(),
         }
     }
 }
@@ -73,34 +223,112 @@ impl<S, F, I> Deref for LkupVec<S, F, I> {
     }
 }
 
+impl<S, F, I> IntoIterator for LkupVec<S, F, I> {
+    type Item = I;
+    type IntoIter = std::vec::IntoIter<I>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_inner().into_iter()
+    }
+}
+
+impl<S, F, I> Extend<I> for LkupVec<S, F, I>
+where
+    S: Store<Pos = usize>,
+    F: Field<S, I>,
+{
+    fn extend<It: IntoIterator<Item = I>>(&mut self, iter: It) {
+        self.load(iter);
+    }
+}
+
+/// Two [`LkupVec`]s are equal, if they contains the same `Item`s (the `Lookup`s are not compared).
+impl<S1, F1, S2, F2, I> PartialEq<LkupVec<S2, F2, I>> for LkupVec<S1, F1, I>
+where
+    I: PartialEq,
+{
+    fn eq(&self, other: &LkupVec<S2, F2, I>) -> bool {
+        self.inner.items == other.inner.items
+    }
+}
+
 impl<S, F, I> LkupVec<S, F, I>
 where
     S: Store<Pos = usize>,
-    F: Fn(&I) -> S::Key,
+    F: Field<S, I>,
 {
     /// Append a new `Item` to the List.
     pub fn push(&mut self, item: I) -> usize {
         let idx = self.inner.items.len();
-        self.inner.store.insert((self.field)(&item), idx);
+        self.field.insert(&mut self.inner.store, &item, idx);
+        self.inner.generation = self.inner.generation.wrapping_add(1);
+        if let Some(key) = self.views.key(&self.field, &item) {
+            self.views.insert(&key, &idx);
+        }
         self.inner.items.push(item);
         idx
     }
 
+    /// Append many `Item`s to the List in one go.
+    /// The `Store` is filled with all new `Item`s at once,
+    /// that is much faster than calling [`LkupVec::push`] for every `Item`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{LkupVec, IndexLookup, Lookup};
+    ///
+    /// #[derive(PartialEq, Debug)]
+    /// struct Car(usize, String);
+    ///
+    /// let mut vec = LkupVec::new(IndexLookup::with_multi_keys(), |c: &Car| c.0);
+    /// vec.load([Car(5, "BMW".into()), Car(1, "Audi".into()), Car(5, "VW".into())]);
+    ///
+    /// assert_eq!(3, vec.len());
+    /// assert_eq!(
+    ///     vec![&Car(5, "BMW".into()), &Car(5, "VW".into())],
+    ///     vec.get_by_lkup_key(5).collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn load<It>(&mut self, items: It)
+    where
+        It: IntoIterator<Item = I>,
+    {
+        let start = self.inner.items.len();
+        self.inner.items.extend(items);
+        self.inner.generation = self.inner.generation.wrapping_add(1);
+
+        self.field.insert_many(
+            &mut self.inner.store,
+            self.inner.items[start..]
+                .iter()
+                .enumerate()
+                .map(|(idx, item)| (item, start + idx)),
+        );
+
+        for (idx, item) in self.inner.items.iter().enumerate().skip(start) {
+            if let Some(key) = self.views.key(&self.field, item) {
+                self.views.insert(&key, &idx);
+            }
+        }
+    }
+
     /// Update an existing `Item` on given index from the List.
     /// If the index exist, the method returns an `Some` with reference to the updated Item.
     /// If not, the method returns `None`.
-    pub fn update<U>(&mut self, index: usize, mut update: U) -> Option<&I>
+    pub fn update<U>(&mut self, index: usize, update: U) -> Option<&I>
     where
         U: FnMut(&mut I),
     {
-        self.inner.items.get_mut(index).map(|item| {
-            let old_key = (self.field)(item);
-            update(item);
-            let new_key = (self.field)(item);
-
-            self.inner.store.update(old_key, index, new_key);
-            &*item
-        })
+        let item = self.inner.items.get_mut(index)?;
+        let old_key = self.views.key(&self.field, item);
+        self.field
+            .update(&mut self.inner.store, item, index, update);
+        if let Some((old_key, new_key)) = old_key.zip(self.views.key(&self.field, item)) {
+            self.views.update(&old_key, &new_key, &index);
+        }
+        self.inner.generation = self.inner.generation.wrapping_add(1);
+        Some(&*item)
     }
 
     /// The Item on index in the list will be removed.
@@ -117,22 +345,32 @@ where
         if index > last_idx {
             return None;
         }
+        self.inner.generation = self.inner.generation.wrapping_add(1);
 
         // last item in the list
         if index == last_idx {
             let rm_item = self.inner.items.remove(index);
-            self.inner.store.delete((self.field)(&rm_item), &index);
+            self.field.delete(&mut self.inner.store, &rm_item, &index);
+            if let Some(key) = self.views.key(&self.field, &rm_item) {
+                self.views.delete(&key, &index);
+            }
             return Some(rm_item);
         }
 
         // remove item and entry in store and swap with last item
         let rm_item = self.inner.items.swap_remove(index);
-        self.inner.store.delete((self.field)(&rm_item), &index);
+        self.field.delete(&mut self.inner.store, &rm_item, &index);
+        if let Some(key) = self.views.key(&self.field, &rm_item) {
+            self.views.delete(&key, &index);
+        }
 
         // formerly last item, now item on index, the swap for the store
         let curr_item = &self.inner.items[index];
-        self.inner.store.delete((self.field)(curr_item), &last_idx);
-        self.inner.store.insert((self.field)(curr_item), index);
+        self.field
+            .update_pos(&mut self.inner.store, curr_item, &last_idx, index);
+        if let Some(key) = self.views.key(&self.field, curr_item) {
+            self.views.update_pos(&key, &last_idx, &index);
+        }
 
         Some(rm_item)
     }
@@ -156,22 +394,314 @@ where
         update_count
     }
 
+    /// Call `update`-function of all items for the `Key`s of a `View` (every `Item` is updated only once).
+    /// Changed lookup `Key`s are re-indexed.
+    /// Return value is the size of updated Items.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{LkupVec, IndexLookup, Lookup};
+    ///
+    /// #[derive(PartialEq, Debug)]
+    /// struct Car(usize, String);
+    ///
+    /// let mut vec = LkupVec::new(IndexLookup::with_multi_keys(), |c: &Car| c.0);
+    /// vec.load([Car(5, "BMW".into()), Car(1, "Audi".into()), Car(2, "VW".into())]);
+    ///
+    /// assert_eq!(2, vec.update_view([1, 2, 1], |c| c.0 = 3));
+    /// assert!(!vec.contains_lkup_key(1));
+    /// assert_eq!(2, vec.get_by_lkup_key(3).count());
+    /// ```
+    pub fn update_view<It, Q, U>(&mut self, keys: It, mut update: U) -> usize
+    where
+        S: Retriever<Q, Pos = usize>,
+        It: IntoIterator<Item = Q>,
+        U: FnMut(&mut I),
+    {
+        let mut positions = self
+            .inner
+            .store
+            .pos_by_many_keys(keys)
+            .copied()
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+        positions.dedup();
+
+        positions
+            .into_iter()
+            .filter(|idx| self.update(*idx, &mut update).is_some())
+            .count()
+    }
+
+    /// Call the `update`-function for every given `Key` with all `Item`s of this `Key`.
+    /// Changed lookup `Key`s are re-indexed.
+    ///
+    /// The `Item`s are collected for all `Key`s, before the first update.
+    /// So every `Item` is updated only once, with the first given `Key`,
+    /// also if its `Key` is changed to a following `Key` or the `Key`s contains duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{LkupVec, HashLookup, Lookup};
+    ///
+    /// #[derive(PartialEq, Debug)]
+    /// struct Car(usize, String);
+    ///
+    /// let mut vec = LkupVec::new(HashLookup::with_multi_keys(), |c: &Car| c.1.clone());
+    /// vec.load([Car(5, "BMW".into()), Car(1, "Audi".into()), Car(2, "BMW".into())]);
+    ///
+    /// vec.for_each_mut_by_key(["BMW", "Audi"], |key, c| c.1 = format!("{key}-{}", c.0));
+    ///
+    /// assert!(vec.contains_lkup_key("BMW-2"));
+    /// assert!(vec.contains_lkup_key("Audi-1"));
+    /// assert!(!vec.contains_lkup_key("BMW"));
+    /// ```
+    pub fn for_each_mut_by_key<It, Q, U>(&mut self, keys: It, mut update: U)
+    where
+        S: Retriever<Q, Pos = usize>,
+        It: IntoIterator<Item = Q>,
+        Q: Clone,
+        U: FnMut(&Q, &mut I),
+    {
+        let mut visited = vec![false; self.inner.items.len()];
+        let updates = keys
+            .into_iter()
+            .map(|key| {
+                let positions = self
+                    .inner
+                    .store
+                    .pos_by_key(key.clone())
+                    .iter()
+                    .filter(|idx| !std::mem::replace(&mut visited[**idx], true))
+                    .copied()
+                    .collect::<Vec<_>>();
+                (key, positions)
+            })
+            .collect::<Vec<_>>();
+
+        for (key, positions) in updates {
+            for idx in positions {
+                self.update(idx, |item| update(&key, item));
+            }
+        }
+    }
+
+    /// Remove all items for the `Key`s of a `View` in one batch.
+    /// In contrast to [`LkupVec::remove_by_many_keys`], the order of the remaining `Item`s is retained
+    /// and the `Store` is rebuild once.
+    /// Return value is the size of removed Items.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{LkupVec, IndexLookup, Lookup};
+    ///
+    /// #[derive(PartialEq, Debug)]
+    /// struct Car(usize, String);
+    ///
+    /// let mut vec = LkupVec::new(IndexLookup::with_multi_keys(), |c: &Car| c.0);
+    /// vec.load([Car(5, "BMW".into()), Car(1, "Audi".into()), Car(2, "VW".into()), Car(3, "Opel".into())]);
+    ///
+    /// assert_eq!(2, vec.remove_view([1, 5]));
+    /// assert_eq!(&[Car(2, "VW".into()), Car(3, "Opel".into())], vec.as_slice());
+    /// ```
+    pub fn remove_view<It, Q>(&mut self, keys: It) -> usize
+    where
+        S: Retriever<Q, Pos = usize>,
+        It: IntoIterator<Item = Q>,
+    {
+        let mut remove = vec![false; self.inner.items.len()];
+        self.inner
+            .store
+            .pos_by_many_keys(keys)
+            .for_each(|idx| remove[*idx] = true);
+
+        let len = self.inner.items.len();
+        let mut idx = 0;
+        self.inner.items.retain(|_| {
+            idx += 1;
+            !remove[idx - 1]
+        });
+
+        let removed = len - self.inner.items.len();
+        if removed > 0 {
+            self.rebuild_store();
+        }
+        removed
+    }
+
     /// Remove all items by a given `Key`.
     /// Return value is the size of removed Items.
     pub fn remove_by_key<Q>(&mut self, key: Q) -> usize
     where
         S: Retriever<Q, Pos = usize>,
-        Q: Clone,
     {
-        let mut remove_count = 0;
+        let positions = self.inner.store.pos_by_key(key).to_vec();
+        self.remove_positions(positions)
+    }
 
-        while let Some(idx) = self.store.pos_by_key(key.clone()).iter().next() {
-            if self.remove(*idx).is_some() {
-                remove_count += 1;
+    /// Remove all items by the given `Key`s.
+    /// Return value is the size of removed Items.
+    ///
+    /// ## Hint:
+    /// Every `Item` is removed by [`LkupVec::remove`] (swap_remove), so the order of the remaining `Item`s is not retained.
+    /// To retain the order, use [`LkupVec::remove_view`].
+    pub fn remove_by_many_keys<It, Q>(&mut self, keys: It) -> usize
+    where
+        S: Retriever<Q, Pos = usize>,
+        It: IntoIterator<Item = Q>,
+    {
+        let positions = self.inner.store.pos_by_many_keys(keys).copied().collect();
+        self.remove_positions(positions)
+    }
+
+    // the positions are removed from the greatest to the smallest,
+    // so the swapped (last) Item is never a Item, which must be removed
+    fn remove_positions(&mut self, mut positions: Vec<usize>) -> usize {
+        positions.sort_unstable_by(|a, b| b.cmp(a));
+        positions.dedup();
+
+        positions
+            .into_iter()
+            .filter(|idx| self.remove(*idx).is_some())
+            .count()
+    }
+
+    /// Removes all `Item`s and clears the `Store`.
+    pub fn clear(&mut self) {
+        self.inner.items.clear();
+        self.inner.store.clear();
+        self.views.clear();
+        self.inner.generation = self.inner.generation.wrapping_add(1);
+    }
+
+    /// Shortens the List, keeping the first `len` `Item`s and drop the rest.
+    /// If `len` is greater or equal to the current length, this has no effect.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.inner.items.len() {
+            return;
+        }
+        self.inner.generation = self.inner.generation.wrapping_add(1);
+
+        for (idx, item) in self.inner.items.iter().enumerate().skip(len) {
+            self.field.delete(&mut self.inner.store, item, &idx);
+            if let Some(key) = self.views.key(&self.field, item) {
+                self.views.delete(&key, &idx);
             }
         }
+        self.inner.items.truncate(len);
+    }
+
+    /// Retains only the `Item`s specified by the predicate.
+    /// The `Store` is rebuild once, after removing all `Item`s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{LkupVec, IndexLookup, Lookup};
+    ///
+    /// #[derive(PartialEq, Debug)]
+    /// struct Car(usize, String);
+    ///
+    /// let mut vec = LkupVec::new(IndexLookup::with_unique_key(), |c: &Car| c.0);
+    /// vec.load([Car(5, "BMW".into()), Car(1, "Audi".into()), Car(2, "VW".into())]);
+    ///
+    /// vec.retain(|c| c.1 != "Audi");
+    ///
+    /// assert_eq!(2, vec.len());
+    /// assert!(!vec.contains_lkup_key(1));
+    /// assert_eq!(Some(&Car(2, "VW".into())), vec.get_by_lkup_key(2).next());
+    /// ```
+    pub fn retain<P>(&mut self, predicate: P)
+    where
+        P: FnMut(&I) -> bool,
+    {
+        let len = self.inner.items.len();
+        self.inner.items.retain(predicate);
+
+        if len != self.inner.items.len() {
+            self.rebuild_store();
+        }
+    }
+
+    /// Retains only the `Item`s, which lookup `Key` is specified by the predicate.
+    /// The `Store` is rebuild once, after removing all `Item`s.
+    pub fn retain_by_lkup_key<P>(&mut self, mut predicate: P)
+    where
+        P: FnMut(&S::Key) -> bool,
+        F: Fn(&I) -> S::Key,
+    {
+        let len = self.inner.items.len();
+        let field = &self.field;
+        self.inner.items.retain(|item| predicate(&field(item)));
+
+        if len != self.inner.items.len() {
+            self.rebuild_store();
+        }
+    }
+
+    /// Removes the specified range from the List and returns all removed `Item`s.
+    /// The `Store` is rebuild once, after removing all `Item`s.
+    pub fn drain<R>(&mut self, range: R) -> std::vec::IntoIter<I>
+    where
+        R: RangeBounds<usize>,
+    {
+        let drained = self.inner.items.drain(range).collect::<Vec<_>>();
+
+        if !drained.is_empty() {
+            self.rebuild_store();
+        }
+
+        drained.into_iter()
+    }
 
-        remove_count
+    /// Sorts the List with a comparator function.
+    /// All `Position`s in the `Store` are renumbered after sorting.
+    pub fn sort_by<C>(&mut self, compare: C)
+    where
+        C: FnMut(&I, &I) -> Ordering,
+    {
+        self.inner.items.sort_by(compare);
+        self.rebuild_store();
+    }
+
+    // create the Store new, for all Items
+    fn rebuild_store(&mut self) {
+        let items = self
+            .inner
+            .items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| (item, idx));
+
+        self.inner.store.clear();
+        self.field.insert_many(&mut self.inner.store, items.clone());
+        self.views.rebuild(&self.field, items);
+        self.inner.generation = self.inner.generation.wrapping_add(1);
+    }
+
+    /// Register a live `View` for the given `Key`s (see: [`crate::collections::live`]).
+    /// The `View` is updated by every write operation, until it is removed by [`LkupVec::unregister_view`].
+    ///
+    /// Hint: by [`LkupVec::rebuild_store_with`] and [`LkupVec::into_ro`] are all registered `View`s removed.
+    pub fn register_view<It>(&mut self, keys: It) -> ViewHandle
+    where
+        It: IntoIterator<Item = S::Key>,
+        F: Fn(&I) -> S::Key,
+        S::Key: Hash + Eq + Clone + Send + Sync + 'static,
+    {
+        self.views.register(
+            &self.inner.store,
+            &self.field,
+            keys,
+            self.inner
+                .items
+                .iter()
+                .enumerate()
+                .map(|(idx, item)| (item, idx)),
+        )
     }
 }
 
@@ -179,7 +709,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lookup::hash::HashLookup;
+    use crate::lookup::{hash::HashLookup, index::IndexLookup};
 
     #[derive(PartialEq, Debug, Clone)]
     struct Person {
@@ -239,6 +769,76 @@ mod tests {
         assert!(view.get_by_key("Anna").next().is_none());
     }
 
+    #[test]
+    fn borrowed_key() {
+        use crate::Borrowed;
+
+        let mut v = LkupVec::new(
+            HashLookup::with_multi_keys(),
+            Borrowed::new(|p: &Person| p.name.as_str()),
+        );
+        v.load([Person::new(1, "Anna"), Person::new(2, "Paul")]);
+        v.push(Person::new(3, "Anna"));
+
+        assert_eq!(
+            vec![&Person::new(1, "Anna"), &Person::new(3, "Anna")],
+            v.get_by_lkup_key("Anna").collect::<Vec<_>>()
+        );
+
+        // the key is not changed, the owned key in the store is the same (not allocated)
+        let key_ptr = v.lkup_ext().keys().find(|k| *k == "Paul").unwrap().as_ptr();
+        v.update(1, |p| p.id = 99);
+        assert_eq!(
+            key_ptr,
+            v.lkup_ext().keys().find(|k| *k == "Paul").unwrap().as_ptr()
+        );
+        assert_eq!(&[1], v.store.pos_by_key("Paul"));
+
+        // the key is changed
+        v.update(1, |p| p.name = String::from("Mario"));
+        assert!(!v.contains_lkup_key("Paul"));
+        assert_eq!(&[1], v.store.pos_by_key("Mario"));
+
+        // remove with swap
+        assert_eq!(Some(Person::new(1, "Anna")), v.remove(0));
+        assert_eq!(&[0], v.store.pos_by_key("Anna"));
+        assert_eq!(&[1], v.store.pos_by_key("Mario"));
+
+        v.sort_by(|a, b| a.name.cmp(&b.name).reverse());
+        assert_eq!(&[1], v.store.pos_by_key("Anna"));
+        assert_eq!(&[0], v.store.pos_by_key("Mario"));
+    }
+
+    #[test]
+    fn borrowed_key_update_panics() {
+        use crate::Borrowed;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut v = LkupVec::new(
+            HashLookup::with_multi_keys(),
+            Borrowed::new(|p: &Person| p.name.as_str()),
+        );
+        v.load([Person::new(1, "Anna"), Person::new(2, "Anna")]);
+
+        // the key is not changed
+        let r = catch_unwind(AssertUnwindSafe(|| {
+            v.update(0, |_| panic!("update failed"));
+        }));
+        assert!(r.is_err());
+        assert_eq!(&[0, 1], v.store.pos_by_key("Anna"));
+
+        // the key is changed before the panic
+        let r = catch_unwind(AssertUnwindSafe(|| {
+            v.update(0, |p| {
+                p.name = String::from("Mario");
+                panic!("update failed")
+            });
+        }));
+        assert!(r.is_err());
+        assert_eq!(&[1], v.store.pos_by_key("Anna"));
+        assert_eq!(&[0], v.store.pos_by_key("Mario"));
+    }
+
     #[test]
     fn remove() {
         let mut v = LkupVec::new(HashLookup::with_multi_keys(), Person::id);
@@ -280,4 +880,408 @@ mod tests {
         v.remove_by_key(&2);
         assert_eq!(0, v.len());
     }
+
+    #[test]
+    fn remove_by_many_keys() {
+        let mut v = LkupVec::new(HashLookup::with_multi_keys(), Person::name);
+        v.load([
+            Person::new(1, "Anna"),
+            Person::new(2, "Paul"),
+            Person::new(3, "Anna"),
+            Person::new(4, "Mario"),
+            Person::new(5, "Anna"),
+            Person::new(6, "Paul"),
+        ]);
+
+        // all Anna's
+        assert_eq!(3, v.remove_by_key("Anna"));
+        assert_eq!(3, v.len());
+        assert!(!v.contains_lkup_key("Anna"));
+        let mut ids = v
+            .get_by_many_lkup_keys(["Paul", "Mario"])
+            .map(|p| p.id)
+            .collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(vec![2, 4, 6], ids);
+
+        // double keys and not existing keys
+        assert_eq!(
+            2,
+            v.remove_by_many_keys(["Paul", "Paul", "Anna", "NotFound"])
+        );
+        assert_eq!(1, v.len());
+        assert_eq!(
+            vec![&Person::new(4, "Mario")],
+            v.get_by_lkup_key("Mario").collect::<Vec<_>>()
+        );
+        assert_eq!(&[0], v.store.pos_by_key("Mario"));
+    }
+
+    #[test]
+    fn clear_and_truncate() {
+        let mut v = LkupVec::new(HashLookup::with_multi_keys(), Person::name);
+        v.load([
+            Person::new(1, "Anna"),
+            Person::new(2, "Paul"),
+            Person::new(3, "Anna"),
+        ]);
+
+        v.truncate(5);
+        assert_eq!(3, v.len());
+
+        v.truncate(2);
+        assert_eq!(2, v.len());
+        assert_eq!(
+            vec![&Person::new(1, "Anna")],
+            v.get_by_lkup_key("Anna").collect::<Vec<_>>()
+        );
+
+        v.truncate(1);
+        assert!(!v.contains_lkup_key("Paul"));
+
+        v.clear();
+        assert!(v.is_empty());
+        assert!(!v.contains_lkup_key("Anna"));
+
+        v.push(Person::new(4, "Paul"));
+        assert_eq!(
+            vec![&Person::new(4, "Paul")],
+            v.get_by_lkup_key("Paul").collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn retain_and_drain() {
+        let mut v = LkupVec::new(HashLookup::with_multi_keys(), Person::name);
+        v.load([
+            Person::new(1, "Anna"),
+            Person::new(2, "Paul"),
+            Person::new(3, "Anna"),
+            Person::new(4, "Mario"),
+            Person::new(5, "Paul"),
+        ]);
+
+        v.retain(|p| p.id != 2);
+        assert_eq!(4, v.len());
+        assert_eq!(
+            vec![&Person::new(5, "Paul")],
+            v.get_by_lkup_key("Paul").collect::<Vec<_>>()
+        );
+
+        v.retain_by_lkup_key(|name| name != "Anna");
+        assert_eq!(2, v.len());
+        assert!(!v.contains_lkup_key("Anna"));
+        assert_eq!(
+            vec![&Person::new(4, "Mario")],
+            v.get_by_lkup_key("Mario").collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![Person::new(4, "Mario")],
+            v.drain(..1).collect::<Vec<_>>()
+        );
+        assert!(!v.contains_lkup_key("Mario"));
+        assert_eq!(
+            vec![&Person::new(5, "Paul")],
+            v.get_by_lkup_key("Paul").collect::<Vec<_>>()
+        );
+        assert_eq!(&Person::new(5, "Paul"), &v[0]);
+    }
+
+    #[test]
+    fn sort_by() {
+        let mut v = LkupVec::new(HashLookup::with_multi_keys(), Person::name);
+        v.load([
+            Person::new(3, "Anna"),
+            Person::new(1, "Paul"),
+            Person::new(2, "Anna"),
+        ]);
+
+        v.sort_by(|a, b| a.id.cmp(&b.id));
+        assert_eq!(vec![1, 2, 3], v.iter().map(|p| p.id).collect::<Vec<_>>());
+        assert_eq!(
+            vec![&Person::new(2, "Anna"), &Person::new(3, "Anna")],
+            v.get_by_lkup_key("Anna").collect::<Vec<_>>()
+        );
+        assert_eq!(&[0], v.store.pos_by_key("Paul"));
+    }
+
+    #[test]
+    fn from_iter_extend_and_into_iter() {
+        let mut v = LkupVec::from_iter(
+            HashLookup::with_multi_keys(),
+            Person::name,
+            [Person::new(1, "Anna"), Person::new(2, "Paul")],
+        );
+        assert!(v.contains_lkup_key("Anna"));
+
+        v.extend([Person::new(3, "Mario"), Person::new(4, "Anna")]);
+        assert_eq!(4, v.len());
+        assert_eq!(
+            vec![&Person::new(1, "Anna"), &Person::new(4, "Anna")],
+            v.get_by_lkup_key("Anna").collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![&Person::new(3, "Mario")],
+            v.get_by_lkup_key("Mario").collect::<Vec<_>>()
+        );
+
+        // equal items with a different lookup
+        let mut other = LkupVec::new(HashLookup::with_unique_key(), Person::id);
+        other.extend(v.iter().cloned());
+        assert!(v == other);
+
+        other.remove(0);
+        assert!(v != other);
+
+        assert_eq!(
+            vec![1, 2, 3, 4],
+            v.into_iter().map(|p| p.id).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn owned_view_refresh() {
+        let mut v = LkupVec::new(HashLookup::with_multi_keys(), Person::name);
+        v.push(Person::new(1, "Anna"));
+        v.push(Person::new(2, "Paul"));
+
+        let mut view = v.create_owned_lkup_view([String::from("Anna"), String::from("Mario")]);
+        assert_eq!(
+            vec![&Person::new(1, "Anna")],
+            v.with_owned_view(&view)
+                .unwrap()
+                .items()
+                .collect::<Vec<_>>()
+        );
+
+        // the view is kept across the mutation, but is stale
+        v.push(Person::new(3, "Mario"));
+        v.push(Person::new(4, "Anna"));
+        assert!(v.with_owned_view(&view).is_none());
+        assert!(!view.key_exist("Mario"));
+
+        v.refresh_owned_view(&mut view);
+        let view = v.with_owned_view(&view).unwrap();
+        assert_eq!(
+            vec![&Person::new(1, "Anna"), &Person::new(4, "Anna")],
+            view.get_by_key("Anna").collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![&Person::new(3, "Mario")],
+            view.get_by_key("Mario").collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn owned_view_refresh_after_remove() {
+        let mut v = LkupVec::new(HashLookup::with_multi_keys(), Person::name);
+        v.push(Person::new(1, "Anna"));
+        v.push(Person::new(2, "Paul"));
+        v.push(Person::new(3, "Anna"));
+        v.push(Person::new(4, "Mario"));
+
+        let mut view = v.create_owned_lkup_view([String::from("Anna")]);
+        assert_eq!(&[0, 2], view.pos_by_key("Anna"));
+
+        // the positions 0 and 2 are invalid after the removes (swap_remove)
+        v.remove(0);
+        v.remove(0);
+        assert_eq!(2, v.len());
+        assert!(v.with_owned_view(&view).is_none());
+
+        // a not changing operation doesn't make the view stale
+        v.refresh_owned_view(&mut view);
+        assert_eq!(None, v.update(5, |p| p.id = 99));
+        assert_eq!(None, v.remove(5));
+
+        let items = v.with_owned_view(&view).unwrap();
+        assert_eq!(
+            vec![&Person::new(3, "Anna")],
+            items.get_by_key("Anna").collect::<Vec<_>>()
+        );
+
+        v.retain(|p| p.id != 3);
+        assert!(v.with_owned_view(&view).is_none());
+        v.refresh_owned_view(&mut view);
+        assert_eq!(0, v.with_owned_view(&view).unwrap().items().count());
+    }
+
+    #[test]
+    fn update_view_with_duplicate_keys() {
+        let mut v = LkupVec::new(IndexLookup::with_multi_keys(), Person::id);
+        v.load([
+            Person::new(1, "Anna"),
+            Person::new(2, "Paul"),
+            Person::new(1, "Mario"),
+            Person::new(3, "Jasmin"),
+        ]);
+        let handle = v.register_view([3, 9]);
+
+        // every Item is updated only once
+        assert_eq!(3, v.update_view([1, 2, 1], |p| p.name.push('!')));
+        assert_eq!(3, v.update_view([1, 2], |p| p.id = 9));
+        assert_eq!(0, v.update_view([1, 7], |p| p.id = 0));
+
+        assert!(!v.contains_lkup_key(1));
+        assert!(!v.contains_lkup_key(2));
+        assert_eq!(
+            vec!["Anna!", "Paul!", "Mario!"],
+            v.get_by_lkup_key(9)
+                .map(|p| p.name.as_str())
+                .collect::<Vec<_>>()
+        );
+
+        let view = v.live_view(handle).unwrap();
+        assert_eq!(&[0, 1, 2], view.pos_by_key(9));
+        assert_eq!(&[3], view.pos_by_key(3));
+    }
+
+    #[test]
+    fn for_each_mut_by_key_change_to_following_key() {
+        let mut v = LkupVec::new(HashLookup::with_multi_keys(), Person::name);
+        v.load([
+            Person::new(1, "Anna"),
+            Person::new(2, "Paul"),
+            Person::new(3, "Anna"),
+        ]);
+        let handle = v.register_view([String::from("Paul")]);
+
+        // Anna is renamed to Paul, but the renamed Items are not updated again by the key Paul
+        let mut calls = 0;
+        v.for_each_mut_by_key(["Anna", "Paul", "Anna"], |key, p| {
+            calls += 1;
+            p.name = String::from(if *key == "Anna" { "Paul" } else { "Mario" });
+        });
+
+        assert_eq!(3, calls);
+        assert!(!v.contains_lkup_key("Anna"));
+        assert_eq!(
+            vec![1, 3],
+            v.get_by_lkup_key("Paul").map(|p| p.id).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![2],
+            v.get_by_lkup_key("Mario").map(|p| p.id).collect::<Vec<_>>()
+        );
+
+        let view = v.live_view(handle).unwrap();
+        assert_eq!(&[0, 2], view.pos_by_key("Paul"));
+    }
+
+    #[test]
+    fn remove_view_retains_order() {
+        let items = [
+            Person::new(5, "BMW"),
+            Person::new(2, "Audi"),
+            Person::new(1, "VW"),
+            Person::new(5, "Opel"),
+            Person::new(3, "Fiat"),
+        ];
+
+        // remove_by_many_keys is a swap_remove
+        let mut v = LkupVec::new(IndexLookup::with_multi_keys(), Person::id);
+        v.load(items.clone());
+        assert_eq!(2, v.remove_by_many_keys([5]));
+        assert_eq!(vec![3, 2, 1], v.iter().map(|p| p.id).collect::<Vec<_>>());
+
+        let mut v = LkupVec::new(IndexLookup::with_multi_keys(), Person::id);
+        v.load(items);
+        let handle = v.register_view([1, 3]);
+
+        assert_eq!(3, v.remove_view([5, 2, 5, 7]));
+        assert_eq!(0, v.remove_view([5]));
+        assert_eq!(vec![1, 3], v.iter().map(|p| p.id).collect::<Vec<_>>());
+
+        // the Store and the live View are re-indexed
+        assert!(!v.contains_lkup_key(5));
+        assert_eq!(Some(&Person::new(3, "Fiat")), v.get_by_lkup_key(3).next());
+        let view = v.live_view(handle).unwrap();
+        assert_eq!(&[0], view.pos_by_key(1));
+        assert_eq!(&[1], view.pos_by_key(3));
+    }
+
+    #[test]
+    fn live_view() {
+        let mut v = LkupVec::new(HashLookup::with_multi_keys(), Person::name);
+        v.push(Person::new(1, "Anna"));
+        v.push(Person::new(2, "Paul"));
+
+        let anna = v.register_view([String::from("Anna"), String::from("Mario")]);
+        v.push(Person::new(3, "Mario"));
+        v.load([Person::new(4, "Anna"), Person::new(5, "Paul")]);
+
+        let view = v.live_view(anna).unwrap();
+        assert_eq!(
+            vec![&Person::new(1, "Anna"), &Person::new(4, "Anna")],
+            view.get_by_key("Anna").collect::<Vec<_>>()
+        );
+        assert!(view.contains_key("Mario"));
+        assert!(!view.contains_key("Paul"));
+
+        // Mario is removed from the view, the last item (Paul) is swapped to position 2
+        v.update(2, |p| p.name = String::from("Paul"));
+        v.remove(0);
+        let view = v.live_view(anna).unwrap();
+        assert!(!view.contains_key("Mario"));
+        assert_eq!(&[3], view.pos_by_key("Anna"));
+        assert_eq!(
+            vec![&Person::new(4, "Anna")],
+            view.get_by_key("Anna").collect::<Vec<_>>()
+        );
+
+        // rebuild the Store
+        v.sort_by(|a, b| b.id.cmp(&a.id));
+        let view = v.live_view(anna).unwrap();
+        assert_eq!(
+            vec![&Person::new(4, "Anna")],
+            view.items().collect::<Vec<_>>()
+        );
+
+        assert!(v.unregister_view(anna));
+        assert!(!v.unregister_view(anna));
+        assert!(v.live_view(anna).is_none());
+    }
+
+    #[test]
+    fn live_view_update_with_unchanged_key() {
+        let mut v = LkupVec::new(HashLookup::with_multi_keys(), Person::name);
+        v.push(Person::new(1, "Anna"));
+        v.push(Person::new(2, "Paul"));
+
+        let anna = v.register_view([String::from("Anna")]);
+        v.update(0, |p| p.id = 7);
+        v.update(1, |p| p.id = 8);
+
+        let view = v.live_view(anna).unwrap();
+        assert_eq!(&[0], view.pos_by_key("Anna"));
+        assert_eq!(
+            vec![&Person::new(7, "Anna")],
+            view.items().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn live_view_handles() {
+        let mut v = LkupVec::new(HashLookup::with_multi_keys(), Person::name);
+        v.push(Person::new(1, "Anna"));
+
+        // the removed Views are not kept
+        for _ in 0..100 {
+            let h = v.register_view([String::from("Anna")]);
+            assert!(v.unregister_view(h));
+        }
+        assert_eq!("[]", format!("{:?}", v.views));
+
+        // a handle of an other collection finds no View
+        let mut other = LkupVec::new(HashLookup::with_multi_keys(), Person::name);
+        other.push(Person::new(2, "Paul"));
+        let paul = other.register_view([String::from("Paul")]);
+        let anna = v.register_view([String::from("Anna")]);
+
+        assert!(v.live_view(paul).is_none());
+        assert!(other.live_view(anna).is_none());
+        assert!(!v.unregister_view(paul));
+        assert!(other.live_view(paul).unwrap().contains_key("Paul"));
+    }
 }
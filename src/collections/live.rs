@@ -0,0 +1,342 @@
+//! The `live` module contains the live `View`s, which are registered on a `read write` collection (e.g. `LkupVec`).
+//!
+//! A `View` created by `create_lkup_view` is a frozen subset of the `Key`s.
+//! A live `View` follows every write operation of the collection, so a new `Item`,
+//! whose `Key` is in the `Key`s of the `View`, is visible without rebuilding the `View`.
+//!
+//! # Example
+//!
+//! ```
+//! use lookups::{LkupVec, HashLookup, Lookup};
+//!
+//! #[derive(PartialEq, Debug)]
+//! struct Car(usize, String);
+//!
+//! let mut vec = LkupVec::new(HashLookup::with_multi_keys(), |c: &Car| c.1.clone());
+//! vec.push(Car(5, "BMW".into()));
+//!
+//! let bmws = vec.register_view([String::from("BMW")]);
+//!
+//! vec.push(Car(1, "Audi".into()));
+//! vec.push(Car(2, "BMW".into()));
+//!
+//! let view = vec.live_view(bmws).unwrap();
+//! assert_eq!(
+//!     vec![&Car(5, "BMW".into()), &Car(2, "BMW".into())],
+//!     view.get_by_key("BMW").collect::<Vec<_>>()
+//! );
+//! assert!(!view.contains_key("Audi"));
+//! ```
+//!
+use crate::lookup::{
+    field::Field,
+    store::{Groups, Positions, Retriever, Store},
+};
+use std::{
+    collections::HashSet,
+    fmt::Debug,
+    hash::Hash,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// The handle for a registered live `View`, which is returned by `register_view`.
+///
+/// Every handle is unique (also over different collections), so the handle of an other collection finds no `View`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ViewHandle(usize);
+
+impl ViewHandle {
+    fn next() -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// The `Store` of a live `View`, which contains only the `Key`s of the `View`.
+#[derive(Debug, Clone)]
+#[repr(transparent)]
+pub struct LiveStore<S>(S);
+
+impl<S, Q> Retriever<Q> for LiveStore<S>
+where
+    S: Retriever<Q>,
+{
+    type Pos = S::Pos;
+
+    fn key_exist(&self, key: Q) -> bool {
+        self.0.key_exist(key)
+    }
+
+    fn pos_by_key(&self, key: Q) -> &[Self::Pos] {
+        self.0.pos_by_key(key)
+    }
+
+    fn count_existing_keys<K>(&self, keys: K) -> usize
+    where
+        K: IntoIterator<Item = Q>,
+    {
+        self.0.count_existing_keys(keys)
+    }
+
+    fn count_by_many_keys<K>(&self, keys: K) -> usize
+    where
+        K: IntoIterator<Item = Q>,
+    {
+        self.0.count_by_many_keys(keys)
+    }
+}
+
+impl<S> Groups for LiveStore<S>
+where
+    S: Groups,
+{
+    type Key = S::Key;
+    type Pos = S::Pos;
+
+    fn groups(&self) -> impl Iterator<Item = (&'_ Self::Key, &'_ [Self::Pos])> {
+        self.0.groups()
+    }
+}
+
+impl<S> Positions for LiveStore<S>
+where
+    S: Groups,
+{
+    type Pos = S::Pos;
+
+    fn positions(&self) -> impl Iterator<Item = &'_ Self::Pos> {
+        self.0.groups().flat_map(|(_, pos)| pos)
+    }
+}
+
+impl<S> Deref for LiveStore<S>
+where
+    S: Deref,
+{
+    type Target = S::Target;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.deref()
+    }
+}
+
+// the type erased `Key`s of a `View`, because the bounds of the `Key` (`Hash + Eq + Clone`)
+// and the `Field` (`Fn(&I) -> S::Key`) are only known by `register`
+trait ViewKeys<S, F, I>: Send + Sync {
+    // creates the `Key` of the `Item` (once for all `View`s)
+    fn key(&self, field: &F, item: &I) -> S::Key
+    where
+        S: Store;
+
+    fn contains(&self, key: &S::Key) -> bool
+    where
+        S: Store;
+
+    fn key_eq(&self, a: &S::Key, b: &S::Key) -> bool
+    where
+        S: Store;
+
+    fn clone_key(&self, key: &S::Key) -> S::Key
+    where
+        S: Store;
+}
+
+struct KeySet<K>(HashSet<K>);
+
+impl<S, F, I> ViewKeys<S, F, I> for KeySet<S::Key>
+where
+    S: Store,
+    F: Fn(&I) -> S::Key,
+    S::Key: Hash + Eq + Clone + Send + Sync,
+{
+    fn key(&self, field: &F, item: &I) -> S::Key {
+        field(item)
+    }
+
+    fn contains(&self, key: &S::Key) -> bool {
+        self.0.contains(key)
+    }
+
+    fn key_eq(&self, a: &S::Key, b: &S::Key) -> bool {
+        a == b
+    }
+
+    fn clone_key(&self, key: &S::Key) -> S::Key {
+        key.clone()
+    }
+}
+
+struct LiveView<S, F, I> {
+    keys: Arc<dyn ViewKeys<S, F, I>>,
+    store: LiveStore<S>,
+}
+
+impl<S: Clone, F, I> Clone for LiveView<S, F, I> {
+    fn clone(&self) -> Self {
+        Self {
+            keys: self.keys.clone(),
+            store: self.store.clone(),
+        }
+    }
+}
+
+/// All registered live `View`s of a collection.
+/// Every write operation of the collection is forwarded to the `View`s, which contains the `Key` of the `Item`.
+///
+/// The [`ViewHandle`]s are unique over all collections, so a handle of an other collection finds no `View`.
+/// A cloned collection shares the handles with the original collection.
+pub(crate) struct LiveViews<S, F, I>(Vec<(ViewHandle, LiveView<S, F, I>)>);
+
+impl<S, F, I> Default for LiveViews<S, F, I> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<S: Clone, F, I> Clone for LiveViews<S, F, I> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S: Debug, F, I> Debug for LiveViews<S, F, I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|(_, v)| &v.store))
+            .finish()
+    }
+}
+
+impl<S, F, I> LiveViews<S, F, I> {
+    pub(crate) fn get(&self, handle: ViewHandle) -> Option<&LiveStore<S>> {
+        self.0
+            .iter()
+            .find(|(h, _)| *h == handle)
+            .map(|(_, v)| &v.store)
+    }
+
+    pub(crate) fn remove(&mut self, handle: ViewHandle) -> bool {
+        match self.0.iter().position(|(h, _)| *h == handle) {
+            Some(idx) => {
+                self.0.swap_remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn views(&mut self) -> impl Iterator<Item = &mut LiveView<S, F, I>> {
+        self.0.iter_mut().map(|(_, v)| v)
+    }
+}
+
+impl<S, F, I> LiveViews<S, F, I>
+where
+    S: Store,
+    F: Field<S, I>,
+{
+    /// Register a new `View` for the given `Key`s, the `Store` is filled with the matching `items`.
+    /// The `Store` of the `View` is created like the `Store` of the collection (see: [`Store::new_like`]).
+    pub(crate) fn register<'a, It, Items>(
+        &mut self,
+        store: &S,
+        field: &F,
+        keys: It,
+        items: Items,
+    ) -> ViewHandle
+    where
+        It: IntoIterator<Item = S::Key>,
+        Items: IntoIterator<Item = (&'a I, S::Pos)>,
+        F: Fn(&I) -> S::Key,
+        S::Key: Hash + Eq + Clone + Send + Sync + 'static,
+        I: 'a,
+    {
+        let keys = KeySet(keys.into_iter().collect::<HashSet<_>>());
+
+        let mut store = store.new_like(0);
+        store.insert_many(items.into_iter().filter_map(|(item, pos)| {
+            let key = field(item);
+            keys.0.contains(&key).then_some((key, pos))
+        }));
+
+        let handle = ViewHandle::next();
+        self.0.push((
+            handle,
+            LiveView {
+                keys: Arc::new(keys),
+                store: LiveStore(store),
+            },
+        ));
+        handle
+    }
+
+    /// Creates the `Key` of the `item`, or `None`, if there are no registered `View`s.
+    pub(crate) fn key(&self, field: &F, item: &I) -> Option<S::Key> {
+        self.0.first().map(|(_, v)| v.keys.key(field, item))
+    }
+
+    pub(crate) fn insert(&mut self, key: &S::Key, pos: &S::Pos)
+    where
+        S::Pos: Clone,
+    {
+        for v in self.views().filter(|v| v.keys.contains(key)) {
+            v.store.0.insert(v.keys.clone_key(key), pos.clone());
+        }
+    }
+
+    /// The `Key` of the `Item` on `pos` is changed from `old_key` to `new_key`,
+    /// the `View`s are only touched, if the `Key` is changed.
+    pub(crate) fn update(&mut self, old_key: &S::Key, new_key: &S::Key, pos: &S::Pos)
+    where
+        S::Pos: Clone,
+    {
+        let changed = self
+            .0
+            .first()
+            .is_some_and(|(_, v)| !v.keys.key_eq(old_key, new_key));
+
+        if changed {
+            self.delete(old_key, pos);
+            self.insert(new_key, pos);
+        }
+    }
+
+    pub(crate) fn update_pos(&mut self, key: &S::Key, old_pos: &S::Pos, new_pos: &S::Pos)
+    where
+        S::Pos: Clone,
+    {
+        for v in self.views().filter(|v| v.keys.contains(key)) {
+            v.store.0.delete(v.keys.clone_key(key), old_pos);
+            v.store.0.insert(v.keys.clone_key(key), new_pos.clone());
+        }
+    }
+
+    pub(crate) fn delete(&mut self, key: &S::Key, pos: &S::Pos) {
+        for v in self.views().filter(|v| v.keys.contains(key)) {
+            v.store.0.delete(v.keys.clone_key(key), pos);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.views().for_each(|v| v.store.0.clear());
+    }
+
+    /// Create all `Store`s new, for the given `items`.
+    pub(crate) fn rebuild<'a, It>(&mut self, field: &F, items: It)
+    where
+        It: IntoIterator<Item = (&'a I, S::Pos)>,
+        S::Pos: Clone,
+        I: 'a,
+    {
+        self.clear();
+        for (item, pos) in items {
+            if let Some(key) = self.key(field, item) {
+                self.insert(&key, &pos);
+            }
+        }
+    }
+}
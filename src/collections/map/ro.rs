@@ -1,9 +1,55 @@
 //! `Read only` implementations for lookup collections `LkupMap` like `HashMap`, `BTreeMap`
 //!
 
-use crate::collections::{map::MapIndex, View};
-use crate::lookup::store::{position::KeyPosition, Lookup, Retriever, Store, ViewCreator};
-use std::{hash::Hash, ops::Deref};
+use crate::collections::{aggregate::Aggregator, map::MapIndex, View};
+use crate::lookup::{
+    frozen::FrozenHashStore,
+    hash::HashStore,
+    store::{
+        self, owned::OwnedView, position::KeyPosition, Groups, Lookup, Positions, Retriever, Store,
+        ViewCreator,
+    },
+};
+use std::{
+    hash::{BuildHasher, Hash},
+    ops::Deref,
+};
+
+pub(crate) use crate::lookup::hash::DefaultHashBuilder;
+
+#[cfg(feature = "hashbrown")]
+pub(crate) type HashMap<K, V, H = DefaultHashBuilder> = hashbrown::HashMap<K, V, H>;
+
+#[cfg(not(feature = "hashbrown"))]
+pub(crate) type HashMap<K, V, H = DefaultHashBuilder> = std::collections::HashMap<K, V, H>;
+
+#[cfg(feature = "hashbrown")]
+pub(crate) use hashbrown::hash_map::Entry;
+#[cfg(feature = "hashbrown")]
+pub(crate) type OccupiedEntry<'a, K, V, H> = hashbrown::hash_map::OccupiedEntry<'a, K, V, H>;
+#[cfg(feature = "hashbrown")]
+pub(crate) type VacantEntry<'a, K, V, H> = hashbrown::hash_map::VacantEntry<'a, K, V, H>;
+
+#[cfg(not(feature = "hashbrown"))]
+pub(crate) use std::collections::hash_map::Entry;
+#[cfg(not(feature = "hashbrown"))]
+pub(crate) type OccupiedEntry<'a, K, V, H> = <H as StdEntries<'a, K, V>>::Occupied;
+#[cfg(not(feature = "hashbrown"))]
+pub(crate) type VacantEntry<'a, K, V, H> = <H as StdEntries<'a, K, V>>::Vacant;
+
+// the std `Entry` types have no `BuildHasher` parameter,
+// this trait maps every `BuildHasher` to the std `Entry` types (for the same aliases like `hashbrown`)
+#[cfg(not(feature = "hashbrown"))]
+pub(crate) trait StdEntries<'a, K: 'a, V: 'a> {
+    type Occupied;
+    type Vacant;
+}
+
+#[cfg(not(feature = "hashbrown"))]
+impl<'a, K: 'a, V: 'a, H> StdEntries<'a, K, V> for H {
+    type Occupied = std::collections::hash_map::OccupiedEntry<'a, K, V>;
+    type Vacant = std::collections::hash_map::VacantEntry<'a, K, V>;
+}
 
 /// [`LkupMap`] is a read only `HashMap` which is extended by a given `Lookup` implementation.
 ///
@@ -42,17 +88,12 @@ use std::{hash::Hash, ops::Deref};
 /// );
 /// ```
 ///
-
-#[cfg(feature = "hashbrown")]
-pub(crate) type HashMap<K, V> = hashbrown::HashMap<K, V>;
-
-#[cfg(not(feature = "hashbrown"))]
-pub(crate) type HashMap<K, V> = std::collections::HashMap<K, V>;
-
 #[derive(Debug, Clone)]
-pub struct LkupHashMap<S, K, V> {
+pub struct LkupHashMap<S, K, V, H = DefaultHashBuilder> {
     pub(crate) store: S,
-    pub(crate) items: HashMap<K, V>,
+    pub(crate) items: HashMap<K, V, H>,
+    // is incremented by every modification of the (rw) collection, to detect a stale `OwnedView`
+    pub(crate) generation: usize,
 }
 
 impl<S, K, V> LkupHashMap<S, K, V>
@@ -66,8 +107,7 @@ where
         F: Fn(&V) -> S::Key,
         K: Clone,
     {
-        let store = lookup.new_map_store(&field, items.iter());
-        Self { store, items }
+        Self::with_hasher(lookup, field, items)
     }
 
     pub fn from_iter<L, P, F, I>(lookup: L, field: F, iter: I) -> Self
@@ -81,6 +121,58 @@ where
         Self::new(lookup, field, HashMap::from_iter(iter))
     }
 
+    /// Create a new [`LkupHashMap`] from the given `HashMap`, the `Store` is created in parallel (feature = "rayon").
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{collections::map::ro::LkupHashMap, HashLookup, Lookup};
+    ///
+    /// let items = (0..100).map(|n: usize| (n, n % 10)).collect();
+    /// let m = LkupHashMap::par_new(HashLookup::with_multi_keys(), |n: &usize| *n, items);
+    ///
+    /// assert_eq!(10, m.get_by_lkup_key(&3).count());
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_new<L, P, F>(lookup: L, field: F, items: HashMap<K, V>) -> Self
+    where
+        L: Lookup<S, P> + Sync,
+        P: KeyPosition<Pos = K>,
+        F: Fn(&V) -> S::Key + Sync,
+        K: Hash + Eq + Clone + Sync,
+        V: Sync,
+        S: store::MergeStore + Send,
+    {
+        let store = lookup.par_new_map_store(&field, &items);
+        Self {
+            store,
+            items,
+            generation: 0,
+        }
+    }
+}
+
+impl<S, K, V, H> LkupHashMap<S, K, V, H>
+where
+    S: Store<Pos = K>,
+{
+    /// Create a new [`LkupHashMap`] from the given `HashMap`,
+    /// the `Item`s are stored with the `BuildHasher` from the `items`.
+    pub fn with_hasher<L, P, F>(lookup: L, field: F, items: HashMap<K, V, H>) -> Self
+    where
+        L: Lookup<S, P>,
+        P: KeyPosition<Pos = K>,
+        F: Fn(&V) -> S::Key,
+        K: Clone,
+    {
+        let store = lookup.new_map_store(&field, items.iter());
+        Self {
+            store,
+            items,
+            generation: 0,
+        }
+    }
+
     pub fn contains_lkup_key<Q>(&self, key: Q) -> bool
     where
         S: Retriever<Q>,
@@ -93,6 +185,7 @@ where
     where
         S: Retriever<Q, Pos = K>,
         K: Hash + Eq,
+        H: BuildHasher,
     {
         self.store.pos_by_key(key).iter().map(|p| &self.items[p])
     }
@@ -102,10 +195,129 @@ where
         S: Retriever<Q, Pos = K>,
         K: Hash + Eq,
         It: IntoIterator<Item = Q>,
+        H: BuildHasher,
     {
         self.store.pos_by_many_keys(keys).map(|p| &self.items[p])
     }
 
+    /// Like [`LkupHashMap::get_by_many_lkup_keys`], but every `Item` is returned only once
+    /// (in the order of the first occurrence).
+    pub fn get_by_unique_many_lkup_keys<It, Q>(&self, keys: It) -> impl Iterator<Item = &V>
+    where
+        S: Retriever<Q, Pos = K>,
+        K: Hash + Eq,
+        H: BuildHasher,
+        It: IntoIterator<Item = Q>,
+    {
+        self.store
+            .pos_by_many_keys_dedup(keys)
+            .map(|p| &self.items[p])
+    }
+
+    /// Like [`LkupHashMap::get_by_unique_many_lkup_keys`], but the `Item`s are ordered by the `Key` of the `HashMap`.
+    pub fn get_by_unique_many_lkup_keys_sorted<It, Q>(&self, keys: It) -> impl Iterator<Item = &V>
+    where
+        S: Retriever<Q, Pos = K>,
+        K: Hash + Eq + Ord,
+        H: BuildHasher,
+        It: IntoIterator<Item = Q>,
+    {
+        self.store
+            .pos_by_many_keys_sorted(keys)
+            .map(|p| &self.items[p])
+    }
+
+    /// Returns all `Key`s with the associated `Item`s, in one pass over the `Store`
+    /// (ordered by the `Key`, if the `Store` is ordered, e.g. `IndexStore`).
+    pub fn group_by_lkup_key(
+        &self,
+    ) -> impl Iterator<Item = (&<S as Groups>::Key, impl Iterator<Item = &V>)>
+    where
+        S: Groups<Pos = K>,
+        K: Hash + Eq,
+        H: BuildHasher,
+    {
+        self.store
+            .groups()
+            .map(|(key, pos)| (key, pos.iter().map(|p| &self.items[p])))
+    }
+
+    /// Returns the count of `Item`s for the given `Key` (without iterating the `Item`s).
+    pub fn count_by_lkup_key<Q>(&self, key: Q) -> usize
+    where
+        S: Retriever<Q>,
+    {
+        self.store.pos_by_key(key).len()
+    }
+
+    /// Returns the count of `Item`s for all given `Key`s.
+    pub fn count_by_many_lkup_keys<It, Q>(&self, keys: It) -> usize
+    where
+        S: Retriever<Q>,
+        It: IntoIterator<Item = Q>,
+    {
+        self.store.count_by_many_keys(keys)
+    }
+
+    /// Returns the count of the given `Key`s, which exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{collections::map::ro::LkupHashMap, IndexLookup, Lookup};
+    ///
+    /// let c = LkupHashMap::from_iter(IndexLookup::with_multi_keys(), |n: &usize| *n, [("a", 1), ("b", 3), ("c", 1)]);
+    ///
+    /// assert_eq!(2, c.count_existing_lkup_keys([1, 3, 7]));
+    /// assert_eq!(vec![7, 9], c.missing_lkup_keys([1, 7, 9]).collect::<Vec<_>>());
+    /// assert_eq!(3, c.count_by_many_lkup_keys([1, 3, 7]));
+    /// ```
+    pub fn count_existing_lkup_keys<It, Q>(&self, keys: It) -> usize
+    where
+        S: Retriever<Q>,
+        It: IntoIterator<Item = Q>,
+    {
+        self.store.count_existing_keys(keys)
+    }
+
+    /// Returns all given `Key`s, which not exist.
+    pub fn missing_lkup_keys<'a, It, Q>(&'a self, keys: It) -> impl Iterator<Item = Q> + 'a
+    where
+        S: Retriever<Q>,
+        It: IntoIterator<Item = Q> + 'a,
+        Q: Clone + 'a,
+    {
+        self.store.missing_keys(keys)
+    }
+
+    /// Returns all `Key`s with the count of the associated `Item`s.
+    pub fn histogram(&self) -> impl Iterator<Item = (&<S as Groups>::Key, usize)>
+    where
+        S: Groups,
+    {
+        self.store.groups().map(|(key, pos)| (key, pos.len()))
+    }
+
+    /// Returns all `Key`s with the aggregated `value`s of the associated `Item`s
+    /// (see: [`crate::collections::aggregate`]).
+    pub fn aggregate_by_lkup_key<'a, F, T, A>(
+        &'a self,
+        value: F,
+        aggregator: A,
+    ) -> impl Iterator<Item = (&'a <S as Groups>::Key, A::Output)> + 'a
+    where
+        S: Groups<Pos = K>,
+        K: Hash + Eq,
+        H: BuildHasher,
+        F: Fn(&'a V) -> T + 'a,
+        A: Aggregator<T> + 'a,
+    {
+        self.store.groups().map(move |(key, pos)| {
+            let values = pos.iter().map(|p| value(&self.items[p]));
+            (key, aggregator.aggregate(values))
+        })
+    }
+
     pub fn lkup_ext(&self) -> &S::Target
     where
         S: Deref,
@@ -116,7 +328,7 @@ where
     pub fn create_lkup_view<'a, It>(
         &'a self,
         keys: It,
-    ) -> View<S::Retriever, MapIndex<'_, HashMap<K, V>>>
+    ) -> View<S::Retriever, MapIndex<'a, HashMap<K, V, H>>>
     where
         S: ViewCreator<'a>,
         It: IntoIterator<Item = <S as ViewCreator<'a>>::Key>,
@@ -124,10 +336,119 @@ where
         let view = self.store.create_view(keys);
         View::new(view, MapIndex(&self.items))
     }
+
+    /// Create a `View` with all `Key`s of the collection (no `Key`s are copied).
+    pub fn create_full_view(&self) -> View<&S, MapIndex<'_, HashMap<K, V, H>>> {
+        View::new(store::View::new(&self.store), MapIndex(&self.items))
+    }
+
+    /// Returns all `Item`s in the order of the `Store`
+    /// (ordered by the `Key`, if the `Store` is ordered, e.g. `IndexStore`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{collections::map::ro::LkupHashMap, IndexLookup, Lookup};
+    ///
+    /// let map = LkupHashMap::from_iter(
+    ///     IndexLookup::with_unique_key(),
+    ///     |n: &usize| *n,
+    ///     [("five", 5), ("one", 1), ("three", 3)],
+    /// );
+    ///
+    /// assert_eq!(vec![&1, &3, &5], map.all_items_by_lkup_order().collect::<Vec<_>>());
+    /// ```
+    pub fn all_items_by_lkup_order(&self) -> impl Iterator<Item = &V>
+    where
+        S: Positions<Pos = K>,
+        K: Hash + Eq,
+        H: BuildHasher,
+    {
+        self.store.positions().map(|p| &self.items[p])
+    }
+
+    /// Create an [`OwnedView`], which is not borrowing the collection.
+    /// The `View` can be used with [`LkupHashMap::with_owned_view`] and refreshed,
+    /// after the collection was modified, with [`LkupHashMap::refresh_owned_view`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{collections::map::ro::LkupHashMap, HashLookup, Lookup};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Car(usize, String);
+    ///
+    /// let map = LkupHashMap::from_iter(
+    ///     HashLookup::with_multi_keys(),
+    ///     |c: &Car| c.1.clone(),
+    ///     [(5, Car(5, "BMW".into())), (1, Car(1, "Audi".into()))],
+    /// );
+    ///
+    /// let bmws = map.create_owned_lkup_view([String::from("BMW")]);
+    ///
+    /// assert_eq!(
+    ///     vec![&Car(5, "BMW".into())],
+    ///     map.with_owned_view(&bmws).unwrap().items().collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn create_owned_lkup_view<'a, LK, It>(&'a self, keys: It) -> OwnedView<LK, S>
+    where
+        It: IntoIterator<Item = LK>,
+        S: Store<Key = LK> + ViewCreator<'a, Key = LK>,
+        <S as ViewCreator<'a>>::Retriever: Groups<Key = LK, Pos = K>,
+        LK: Clone,
+        K: Clone,
+    {
+        let mut view = OwnedView::new(&self.store, keys);
+        view.generation = self.generation;
+        view
+    }
+
+    /// Refresh the given [`OwnedView`] (see: [`LkupHashMap::create_owned_lkup_view`]).
+    pub fn refresh_owned_view<'a, LK>(&'a self, view: &mut OwnedView<LK, S>)
+    where
+        S: Store<Key = LK> + ViewCreator<'a, Key = LK>,
+        <S as ViewCreator<'a>>::Retriever: Groups<Key = LK, Pos = K>,
+        LK: Clone,
+        K: Clone,
+    {
+        view.refresh(&self.store);
+        view.generation = self.generation;
+    }
+
+    /// Combine the given [`OwnedView`] with the `Item`s of this collection to a [`View`].
+    ///
+    /// Returns `None`, if the collection was modified after the [`OwnedView`] was created or refreshed,
+    /// because the `Position`s of the `View` can be invalid (see: [`LkupHashMap::refresh_owned_view`]).
+    #[allow(clippy::type_complexity)]
+    pub fn with_owned_view<'a, LK>(
+        &'a self,
+        view: &'a OwnedView<LK, S>,
+    ) -> Option<View<&'a OwnedView<LK, S>, MapIndex<'a, HashMap<K, V, H>>>> {
+        (view.generation == self.generation)
+            .then(|| View::new(store::View::new(view), MapIndex(&self.items)))
+    }
+}
+
+impl<LK, P, LH, K, V, H> LkupHashMap<HashStore<LK, P, LH>, K, V, H>
+where
+    LK: Hash + Eq,
+    P: KeyPosition,
+    LH: BuildHasher + Clone,
+{
+    /// Convert the `HashStore` into a [`FrozenHashStore`], which needs less memory.
+    pub fn freeze(self) -> LkupHashMap<FrozenHashStore<LK, P, LH>, K, V, H> {
+        LkupHashMap {
+            store: self.store.freeze(),
+            items: self.items,
+            generation: self.generation,
+        }
+    }
 }
 
-impl<S, K, V> Deref for LkupHashMap<S, K, V> {
-    type Target = HashMap<K, V>;
+impl<S, K, V, H> Deref for LkupHashMap<S, K, V, H> {
+    type Target = HashMap<K, V, H>;
 
     fn deref(&self) -> &Self::Target {
         &self.items
@@ -168,4 +489,135 @@ mod tests {
             m2.get_by_many_lkup_keys([99, 1]).collect::<Vec<_>>()
         );
     }
+
+    #[test]
+    fn new_and_with_hasher() {
+        use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+        // the default hasher is inferred by `new`
+        let m = LkupHashMap::new(
+            IndexLookup::with_unique_key(),
+            |c: &usize| *c,
+            (0..5usize).map(|n| (n, n * 2)).collect(),
+        );
+        assert!(m.contains_lkup_key(8));
+
+        let mut items = HashMap::with_hasher(BuildHasherDefault::<DefaultHasher>::default());
+        items.insert(1, Car(5, "BMW".into()));
+        let m = LkupHashMap::with_hasher(IndexLookup::with_unique_key(), |c: &Car| c.0, items);
+        assert_eq!(Some(&Car(5, "BMW".into())), m.get_by_lkup_key(5).next());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_new() {
+        use crate::HashLookup;
+
+        let items = (0..10_000u16)
+            .map(|n| (n, Car(n % 1_000, format!("Car-{}", n % 777))))
+            .collect::<HashMap<_, _>>();
+
+        let idx = LkupHashMap::new(IndexLookup::with_multi_keys(), |c: &Car| c.0, items.clone());
+        let par_idx =
+            LkupHashMap::par_new(IndexLookup::with_multi_keys(), |c: &Car| c.0, items.clone());
+        for key in 0..1_000 {
+            assert_eq!(idx.store.pos_by_key(key), par_idx.store.pos_by_key(key));
+        }
+
+        let hash = LkupHashMap::new(
+            HashLookup::with_multi_keys(),
+            |c: &Car| c.1.clone(),
+            items.clone(),
+        );
+        let par_hash =
+            LkupHashMap::par_new(HashLookup::with_multi_keys(), |c: &Car| c.1.clone(), items);
+        for key in hash.lkup_ext().keys() {
+            assert_eq!(hash.store.pos_by_key(key), par_hash.store.pos_by_key(key));
+        }
+        assert_eq!(
+            hash.lkup_ext().keys().count(),
+            par_hash.lkup_ext().keys().count()
+        );
+    }
+
+    #[test]
+    fn frozen_store() {
+        use crate::{lookup::frozen::FrozenHashLookup, HashLookup};
+
+        let cars = [
+            (String::from("Audi"), Car(99, "Audi".into())),
+            (String::from("BMW"), Car(1, "BMW".into())),
+        ];
+
+        let m = LkupHashMap::from_iter(
+            FrozenHashLookup::with_unique_key(),
+            |c: &Car| c.1.clone(),
+            cars.clone(),
+        );
+        assert!(m.contains_lkup_key("BMW"));
+        assert_eq!(
+            &Car(99, "Audi".into()),
+            m.get_by_lkup_key("Audi").next().unwrap()
+        );
+
+        let m = LkupHashMap::from_iter(HashLookup::with_unique_key(), |c: &Car| c.1.clone(), cars)
+            .freeze();
+        assert!(m.contains_lkup_key("Audi"));
+        assert!(!m.contains_lkup_key("VW"));
+
+        let view = m.create_lkup_view([String::from("BMW")]);
+        assert_eq!(
+            vec![&Car(1, "BMW".into())],
+            view.items().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn group_by_lkup_key() {
+        let cars = [
+            (String::from("Audi"), Car(99, "Audi".into())),
+            (String::from("BMW"), Car(1, "BMW".into())),
+            (String::from("VW"), Car(1, "VW".into())),
+        ];
+        let m = LkupHashMap::from_iter(IndexLookup::with_multi_keys(), |c: &Car| c.0, cars);
+
+        let groups = m
+            .group_by_lkup_key()
+            .map(|(key, cars)| {
+                let mut cars = cars.map(|c| c.1.as_str()).collect::<Vec<_>>();
+                cars.sort();
+                (*key, cars)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(1, vec!["BMW", "VW"]), (99, vec!["Audi"])], groups);
+    }
+
+    #[test]
+    fn aggregate_by_lkup_key() {
+        use crate::collections::aggregate::{Min, Sum};
+
+        let cars = [
+            (String::from("Audi"), Car(99, "Audi".into())),
+            (String::from("BMW"), Car(1, "BMW".into())),
+            (String::from("VW"), Car(1, "VW".into())),
+        ];
+        let m = LkupHashMap::from_iter(IndexLookup::with_multi_keys(), |c: &Car| c.0, cars);
+
+        assert_eq!(2, m.count_by_lkup_key(1));
+        assert_eq!(0, m.count_by_lkup_key(7));
+        assert_eq!(vec![(&1, 2), (&99, 1)], m.histogram().collect::<Vec<_>>());
+        assert_eq!(
+            vec![(&1, 2), (&99, 99)],
+            m.aggregate_by_lkup_key(|c| c.0, Sum).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(&1, Some("BMW")), (&99, Some("Audi"))],
+            m.aggregate_by_lkup_key(|c| c.1.as_str(), Min)
+                .collect::<Vec<_>>()
+        );
+
+        let view = m.create_lkup_view([1]);
+        assert_eq!(2, view.count_by_key(1));
+        assert_eq!(vec![(&1, 2)], view.histogram().collect::<Vec<_>>());
+    }
 }
@@ -2,76 +2,320 @@
 //!
 
 use crate::{
-    collections::map::ro,
-    lookup::store::{position::KeyPosition, Lookup, Retriever, Store},
+    collections::{
+        live::{LiveStore, LiveViews, ViewHandle},
+        map::{ro, MapIndex},
+        View,
+    },
+    lookup::{
+        field::Field,
+        store::{self, position::KeyPosition, Lookup, Retriever, Store},
+    },
+};
+use std::{
+    collections::HashSet,
+    hash::{BuildHasher, Hash},
+    ops::Deref,
 };
-use std::{hash::Hash, ops::Deref};
 
 #[derive(Debug, Clone)]
-pub struct LkupHashMap<S, F, K, V> {
+pub struct LkupHashMap<S, F, K, V, H = ro::DefaultHashBuilder> {
     field: F,
-    inner: ro::LkupHashMap<S, K, V>,
+    inner: ro::LkupHashMap<S, K, V, H>,
+    views: LiveViews<S, F, V>,
 }
 
 impl<S, F, K, V> LkupHashMap<S, F, K, V>
 where
     S: Store<Pos = K>,
-    F: Fn(&V) -> S::Key,
+    F: Field<S, V>,
 {
     pub fn new<L, P>(lookup: L, field: F) -> Self
     where
         L: Lookup<S, P>,
         P: KeyPosition<Pos = K>,
-        K: Clone,
     {
         Self {
-            inner: ro::LkupHashMap::new(lookup, &field, ro::HashMap::new()),
+            inner: ro::LkupHashMap {
+                store: lookup.new_store(0),
+                items: ro::HashMap::new(),
+                generation: 0,
+            },
+            field,
+            views: LiveViews::default(),
+        }
+    }
+
+    /// Create a new [`LkupHashMap`] from the given `Key`-`Item` pairs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{LkupHashMap, IndexLookup, Lookup};
+    ///
+    /// #[derive(PartialEq, Debug)]
+    /// struct Car(usize, String);
+    ///
+    /// let cars = [("BMW", Car(5, "BMW".into())), ("Audi", Car(1, "Audi".into()))];
+    /// let map = LkupHashMap::from_iter(IndexLookup::with_unique_key(), |c: &Car| c.0, cars);
+    ///
+    /// assert!(map.contains_lkup_key(1));
+    /// assert_eq!(&Car(5, "BMW".into()), map.into_inner().get("BMW").unwrap());
+    /// ```
+    pub fn from_iter<L, P, It>(lookup: L, field: F, iter: It) -> Self
+    where
+        L: Lookup<S, P>,
+        P: KeyPosition<Pos = K>,
+        It: IntoIterator<Item = (K, V)>,
+        K: Hash + Eq + Clone,
+    {
+        let items = ro::HashMap::from_iter(iter);
+        let mut store = lookup.new_store(items.len());
+        field.insert_many(&mut store, items.iter().map(|(k, v)| (v, k.clone())));
+
+        Self {
+            inner: ro::LkupHashMap {
+                store,
+                items,
+                generation: 0,
+            },
+            field,
+            views: LiveViews::default(),
+        }
+    }
+}
+
+impl<S, F, K, V, H> LkupHashMap<S, F, K, V, H>
+where
+    S: Store<Pos = K>,
+    F: Field<S, V>,
+{
+    /// Create a new empty [`LkupHashMap`], where the `Item`s are stored with the given `BuildHasher`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{LkupHashMap, IndexLookup, Lookup};
+    /// use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+    ///
+    /// let mut map = LkupHashMap::with_hasher(
+    ///     IndexLookup::with_unique_key(),
+    ///     |c: &(usize, &str)| c.0,
+    ///     BuildHasherDefault::<DefaultHasher>::default(),
+    /// );
+    ///
+    /// map.insert("BMW", (5, "BMW"));
+    /// assert!(map.contains_lkup_key(5));
+    /// ```
+    pub fn with_hasher<L, P>(lookup: L, field: F, hash_builder: H) -> Self
+    where
+        L: Lookup<S, P>,
+        P: KeyPosition<Pos = K>,
+    {
+        Self {
+            inner: ro::LkupHashMap {
+                store: lookup.new_store(0),
+                items: ro::HashMap::with_hasher(hash_builder),
+                generation: 0,
+            },
+            field,
+            views: LiveViews::default(),
+        }
+    }
+}
+
+impl<S, F, K, V, H> LkupHashMap<S, F, K, V, H> {
+    /// Consumes the [`LkupHashMap`] and returns the inner `HashMap` with all `Item`s.
+    pub fn into_inner(self) -> ro::HashMap<K, V, H> {
+        self.inner.items
+    }
+
+    /// Consumes the [`LkupHashMap`] and returns a read only [`ro::LkupHashMap`], the `Store` is reused.
+    pub fn into_ro(self) -> ro::LkupHashMap<S, K, V, H> {
+        self.inner
+    }
+
+    /// Consumes the [`LkupHashMap`] and returns a new [`LkupHashMap`] with the same `Item`s,
+    /// where the `Store` is created by the given `lookup` and `field`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{LkupHashMap, HashLookup, IndexLookup, Lookup};
+    ///
+    /// let map = LkupHashMap::from_iter(IndexLookup::with_unique_key(), |id: &usize| *id, [("a", 3)]);
+    ///
+    /// // switch from an IndexLookup to a HashLookup with a String Key
+    /// let map = map.rebuild_store_with(HashLookup::with_unique_key(), |id: &usize| id.to_string());
+    /// assert!(map.contains_lkup_key("3"));
+    /// ```
+    pub fn rebuild_store_with<S2, F2, L, P>(
+        self,
+        lookup: L,
+        field: F2,
+    ) -> LkupHashMap<S2, F2, K, V, H>
+    where
+        S2: Store<Pos = K>,
+        F2: Field<S2, V>,
+        L: Lookup<S2, P>,
+        P: KeyPosition<Pos = K>,
+        K: Clone,
+    {
+        let items = self.inner.items;
+        let mut store = lookup.new_store(items.len());
+        field.insert_many(&mut store, items.iter().map(|(k, v)| (v, k.clone())));
+
+        LkupHashMap {
             field,
+            inner: ro::LkupHashMap {
+                store,
+                items,
+                generation: 0,
+            },
+            views: LiveViews::default(),
         }
     }
+
+    /// Returns the live `View` for the given `handle` (see: [`LkupHashMap::register_view`]),
+    /// or `None`, if the `View` was removed.
+    #[allow(clippy::type_complexity)]
+    pub fn live_view(
+        &self,
+        handle: ViewHandle,
+    ) -> Option<View<&LiveStore<S>, MapIndex<'_, ro::HashMap<K, V, H>>>> {
+        self.views
+            .get(handle)
+            .map(|s| View::new(store::View::new(s), MapIndex(&self.inner.items)))
+    }
+
+    /// Removes the live `View` for the given `handle`.
+    /// Returns `false`, if the `View` was already removed.
+    pub fn unregister_view(&mut self, handle: ViewHandle) -> bool {
+        self.views.remove(handle)
+    }
 }
 
-impl<S, F, K, V> Deref for LkupHashMap<S, F, K, V> {
-    type Target = ro::LkupHashMap<S, K, V>;
+impl<S, K, V, H> ro::LkupHashMap<S, K, V, H> {
+    /// Consumes the read only [`ro::LkupHashMap`] and returns a [`LkupHashMap`], the `Store` is reused.
+    ///
+    /// The `field` must create the same `Key`s, which are used by creating the `Store`.
+    pub fn into_rw<F>(self, field: F) -> LkupHashMap<S, F, K, V, H>
+    where
+        S: Store<Pos = K>,
+        F: Field<S, V>,
+    {
+        LkupHashMap {
+            field,
+            inner: self,
+            views: LiveViews::default(),
+        }
+    }
+}
+
+impl<S, F, K, V, H> IntoIterator for LkupHashMap<S, F, K, V, H> {
+    type Item = (K, V);
+    type IntoIter = <ro::HashMap<K, V, H> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_inner().into_iter()
+    }
+}
+
+impl<S, F, K, V, H> Extend<(K, V)> for LkupHashMap<S, F, K, V, H>
+where
+    S: Store<Pos = K>,
+    F: Field<S, V>,
+    K: Hash + Eq + Clone,
+    H: BuildHasher,
+{
+    fn extend<It: IntoIterator<Item = (K, V)>>(&mut self, iter: It) {
+        let iter = iter.into_iter();
+        let (additional, _) = iter.size_hint();
+        self.inner.items.reserve(additional);
+        self.inner.store.reserve(additional);
+
+        iter.for_each(|(key, item)| {
+            self.insert(key, item);
+        });
+    }
+}
+
+/// Two [`LkupHashMap`]s are equal, if they contains the same `Key`-`Item` pairs (the `Lookup`s are not compared).
+impl<S1, F1, S2, F2, K, V, H> PartialEq<LkupHashMap<S2, F2, K, V, H>>
+    for LkupHashMap<S1, F1, K, V, H>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+    H: BuildHasher,
+{
+    fn eq(&self, other: &LkupHashMap<S2, F2, K, V, H>) -> bool {
+        self.inner.items == other.inner.items
+    }
+}
+
+impl<S, F, K, V, H> Deref for LkupHashMap<S, F, K, V, H> {
+    type Target = ro::LkupHashMap<S, K, V, H>;
 
     fn deref(&self) -> &Self::Target {
         &self.inner
     }
 }
 
-impl<S, F, K, V> LkupHashMap<S, F, K, V>
+impl<S, F, K, V, H> LkupHashMap<S, F, K, V, H>
 where
     S: Store<Pos = K>,
-    F: Fn(&V) -> S::Key,
+    F: Field<S, V>,
     K: Hash + Eq,
+    H: BuildHasher,
 {
     /// Insert a new `Item` to the Map.
+    /// If the Map already contains the `key`, the old `Item` is replaced and returned.
     pub fn insert(&mut self, key: K, item: V) -> Option<V>
     where
         K: Hash + Eq + Clone,
     {
-        self.inner.store.insert((self.field)(&item), key.clone());
+        if let Some(old) = self.inner.items.get(&key) {
+            self.field.delete(&mut self.inner.store, old, &key);
+            if let Some(old_key) = self.views.key(&self.field, old) {
+                self.views.delete(&old_key, &key);
+            }
+        }
+
+        self.field.insert(&mut self.inner.store, &item, key.clone());
+        if let Some(new_key) = self.views.key(&self.field, &item) {
+            self.views.insert(&new_key, &key);
+        }
+        self.inner.generation = self.inner.generation.wrapping_add(1);
         self.inner.items.insert(key, item)
     }
 
     /// Update an existing `Item` on given key from the Map.
     /// If the key exist, the method returns an `Some` with reference to the updated Item.
     /// If not, the method returns `None`.
-    pub fn update<U>(&mut self, key: K, mut update: U) -> Option<&V>
+    pub fn update<U>(&mut self, key: K, update: U) -> Option<&V>
     where
         U: FnMut(&mut V),
+        K: Clone,
     {
         let v = self.inner.items.get_mut(&key)?;
-        let old_key = (self.field)(v);
-        update(v);
-        self.inner.store.update(old_key, key, (self.field)(v));
+        let old_key = self.views.key(&self.field, v);
+        self.field
+            .update(&mut self.inner.store, v, key.clone(), update);
+        if let Some((old_key, new_key)) = old_key.zip(self.views.key(&self.field, v)) {
+            self.views.update(&old_key, &new_key, &key);
+        }
+        self.inner.generation = self.inner.generation.wrapping_add(1);
         Some(v)
     }
 
     /// The Item on index in the list will be removed.
     pub fn remove(&mut self, key: K) -> Option<V> {
         let removed = self.inner.items.remove(&key)?;
-        self.inner.store.delete((self.field)(&removed), &key);
+        self.field.delete(&mut self.inner.store, &removed, &key);
+        if let Some(old_key) = self.views.key(&self.field, &removed) {
+            self.views.delete(&old_key, &key);
+        }
+        self.inner.generation = self.inner.generation.wrapping_add(1);
         Some(removed)
     }
 
@@ -95,23 +339,346 @@ where
         update_count
     }
 
+    /// Call `update`-function of all items for the `Key`s of a `View` (every `Item` is updated only once).
+    /// Changed lookup `Key`s are re-indexed.
+    /// Return value is the size of updated Items.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{LkupHashMap, IndexLookup, Lookup};
+    ///
+    /// #[derive(PartialEq, Debug)]
+    /// struct Car(usize, String);
+    ///
+    /// let mut map = LkupHashMap::new(IndexLookup::with_multi_keys(), |c: &Car| c.0);
+    /// map.insert("BMW", Car(5, "BMW".into()));
+    /// map.insert("Audi", Car(1, "Audi".into()));
+    ///
+    /// assert_eq!(2, map.update_view([1, 5, 1], |c| c.0 = 3));
+    /// assert_eq!(2, map.get_by_lkup_key(3).count());
+    /// ```
+    pub fn update_view<It, Q, U>(&mut self, keys: It, mut update: U) -> usize
+    where
+        S: Retriever<Q, Pos = K>,
+        It: IntoIterator<Item = Q>,
+        U: FnMut(&mut V),
+        K: Clone,
+    {
+        let mut positions = HashSet::new();
+        let positions = self
+            .inner
+            .store
+            .pos_by_many_keys(keys)
+            .filter(|pos| positions.insert(*pos))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        positions
+            .into_iter()
+            .filter(|pos| self.update(pos.clone(), &mut update).is_some())
+            .count()
+    }
+
+    /// Call the `update`-function for every given `Key` with all `Item`s of this `Key`.
+    /// Changed lookup `Key`s are re-indexed.
+    ///
+    /// The `Item`s are collected for all `Key`s, before the first update.
+    /// So every `Item` is updated only once, with the first given `Key`,
+    /// also if its `Key` is changed to a following `Key` or the `Key`s contains duplicates.
+    pub fn for_each_mut_by_key<It, Q, U>(&mut self, keys: It, mut update: U)
+    where
+        S: Retriever<Q, Pos = K>,
+        It: IntoIterator<Item = Q>,
+        Q: Clone,
+        U: FnMut(&Q, &mut V),
+        K: Clone,
+    {
+        let mut visited = HashSet::new();
+        let updates = keys
+            .into_iter()
+            .map(|key| {
+                let positions = self
+                    .inner
+                    .store
+                    .pos_by_key(key.clone())
+                    .iter()
+                    .filter(|pos| visited.insert(*pos))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                (key, positions)
+            })
+            .collect::<Vec<_>>();
+
+        for (key, positions) in updates {
+            for pos in positions {
+                self.update(pos, |item| update(&key, item));
+            }
+        }
+    }
+
+    /// Remove all items for the `Key`s of a `View`.
+    /// Every `Item` is removed by the `Key` of the `HashMap`, so this is the same like [`LkupHashMap::remove_by_many_keys`].
+    /// Return value is the size of removed Items.
+    pub fn remove_view<It, Q>(&mut self, keys: It) -> usize
+    where
+        S: Retriever<Q, Pos = K>,
+        It: IntoIterator<Item = Q>,
+        K: Clone,
+    {
+        self.remove_by_many_keys(keys)
+    }
+
     /// Remove all items by a given `Key`.
     /// Return value is the size of removed Items.
     pub fn remove_by_key<Q>(&mut self, key: Q) -> usize
     where
         S: Retriever<Q, Pos = K>,
-        Q: Clone,
         K: Clone,
     {
-        let mut remove_count = 0;
+        let positions = self.inner.store.pos_by_key(key).to_vec();
+        self.remove_positions(positions)
+    }
+
+    /// Remove all items by the given `Key`s.
+    /// Return value is the size of removed Items.
+    pub fn remove_by_many_keys<It, Q>(&mut self, keys: It) -> usize
+    where
+        S: Retriever<Q, Pos = K>,
+        It: IntoIterator<Item = Q>,
+        K: Clone,
+    {
+        let positions = self.inner.store.pos_by_many_keys(keys).cloned().collect();
+        self.remove_positions(positions)
+    }
 
-        while let Some(idx) = self.store.pos_by_key(key.clone()).iter().next() {
-            if self.remove(idx.clone()).is_some() {
-                remove_count += 1;
+    // double positions are ignored, because the second remove found no Item
+    fn remove_positions(&mut self, positions: Vec<K>) -> usize {
+        positions
+            .into_iter()
+            .filter_map(|pos| self.remove(pos))
+            .count()
+    }
+
+    /// Register a live `View` for the given `Key`s (see: [`crate::collections::live`]).
+    /// The `View` is updated by every write operation, until it is removed by [`LkupHashMap::unregister_view`].
+    ///
+    /// Hint: by [`LkupHashMap::rebuild_store_with`] and [`LkupHashMap::into_ro`] are all registered `View`s removed.
+    pub fn register_view<It>(&mut self, keys: It) -> ViewHandle
+    where
+        It: IntoIterator<Item = S::Key>,
+        F: Fn(&V) -> S::Key,
+        S::Key: Hash + Eq + Clone + Send + Sync + 'static,
+        K: Clone,
+    {
+        self.views.register(
+            &self.inner.store,
+            &self.field,
+            keys,
+            self.inner.items.iter().map(|(k, v)| (v, k.clone())),
+        )
+    }
+
+    /// Gets the given key's corresponding [`Entry`] in the Map for in-place manipulation.
+    /// All modifications by the `Entry` keep the `Lookup` in sync.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{LkupHashMap, IndexLookup, Lookup};
+    ///
+    /// #[derive(PartialEq, Debug)]
+    /// struct Car(usize, String);
+    ///
+    /// let mut map = LkupHashMap::new(IndexLookup::with_unique_key(), |c: &Car| c.0);
+    ///
+    /// // insert, because the key doesn't exist
+    /// map.entry(String::from("BMW")).or_insert(Car(1, "BMW".into()));
+    /// // modify, because the key exist
+    /// map.entry(String::from("BMW"))
+    ///    .and_modify(|c| c.0 = 5)
+    ///    .or_insert(Car(1, "BMW".into()));
+    ///
+    /// assert!(!map.contains_lkup_key(1));
+    /// assert_eq!(Some(&Car(5, "BMW".into())), map.get_by_lkup_key(5).next());
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, S, F, K, V, H> {
+        let field = &self.field;
+        let store = &mut self.inner.store;
+        let views = &mut self.views;
+        let generation = &mut self.inner.generation;
+
+        match self.inner.items.entry(key) {
+            ro::Entry::Occupied(entry) => Entry::Occupied(OccupiedEntry {
+                field,
+                store,
+                views,
+                generation,
+                entry,
+            }),
+            ro::Entry::Vacant(entry) => Entry::Vacant(VacantEntry {
+                field,
+                store,
+                views,
+                generation,
+                entry,
+            }),
+        }
+    }
+}
+
+/// A view into a single entry in a [`LkupHashMap`], which may either be vacant or occupied.
+///
+/// This `enum` is constructed from the [`LkupHashMap::entry`] method.
+///
+/// ## Hint:
+/// In contrast to the [`std::collections::hash_map::Entry`], there is no `&mut V` returned,
+/// because a modification of the `Item` must be in sync with the `Lookup`.
+pub enum Entry<'a, S, F, K, V, H = ro::DefaultHashBuilder> {
+    Occupied(OccupiedEntry<'a, S, F, K, V, H>),
+    Vacant(VacantEntry<'a, S, F, K, V, H>),
+}
+
+impl<'a, S, F, K, V, H> Entry<'a, S, F, K, V, H>
+where
+    S: Store<Pos = K>,
+    F: Field<S, V>,
+    K: Hash + Eq + Clone,
+    H: BuildHasher,
+{
+    /// Returns a reference to this entry's key.
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default if empty,
+    /// and returns a reference to the value in the entry.
+    pub fn or_insert(self, default: V) -> &'a V {
+        match self {
+            Entry::Occupied(entry) => entry.into_ref(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function if empty,
+    /// and returns a reference to the value in the entry.
+    pub fn or_insert_with<D>(self, default: D) -> &'a V
+    where
+        D: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_ref(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any potential inserts into the map.
+    pub fn and_modify<U>(self, update: U) -> Self
+    where
+        U: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut entry) => {
+                entry.update(update);
+                Entry::Occupied(entry)
             }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+/// A view into an occupied entry in a [`LkupHashMap`]. It is part of the [`Entry`] enum.
+pub struct OccupiedEntry<'a, S, F, K, V, H = ro::DefaultHashBuilder> {
+    field: &'a F,
+    store: &'a mut S,
+    views: &'a mut LiveViews<S, F, V>,
+    generation: &'a mut usize,
+    entry: ro::OccupiedEntry<'a, K, V, H>,
+}
+
+impl<'a, S, F, K, V, H> OccupiedEntry<'a, S, F, K, V, H>
+where
+    S: Store<Pos = K>,
+    F: Field<S, V>,
+    K: Hash + Eq + Clone,
+    H: BuildHasher,
+{
+    /// Gets a reference to the key in the entry.
+    pub fn key(&self) -> &K {
+        self.entry.key()
+    }
+
+    /// Gets a reference to the value in the entry.
+    pub fn get(&self) -> &V {
+        self.entry.get()
+    }
+
+    /// Converts the entry into a reference to the value in the entry with a lifetime bound to the map itself.
+    pub fn into_ref(self) -> &'a V {
+        self.entry.into_mut()
+    }
+
+    /// Update the value in the entry and returns a reference to the updated value.
+    pub fn update<U>(&mut self, update: U) -> &V
+    where
+        U: FnOnce(&mut V),
+    {
+        let pos = self.entry.key().clone();
+        let item = self.entry.get_mut();
+        let old_key = self.views.key(self.field, item);
+        self.field.update(self.store, item, pos.clone(), update);
+        if let Some((old_key, new_key)) = old_key.zip(self.views.key(self.field, item)) {
+            self.views.update(&old_key, &new_key, &pos);
         }
+        *self.generation = self.generation.wrapping_add(1);
+        item
+    }
 
-        remove_count
+    /// Takes the value out of the entry, and returns it.
+    pub fn remove(self) -> V {
+        let (pos, item) = self.entry.remove_entry();
+        self.field.delete(self.store, &item, &pos);
+        if let Some(key) = self.views.key(self.field, &item) {
+            self.views.delete(&key, &pos);
+        }
+        *self.generation = self.generation.wrapping_add(1);
+        item
+    }
+}
+
+/// A view into a vacant entry in a [`LkupHashMap`]. It is part of the [`Entry`] enum.
+pub struct VacantEntry<'a, S, F, K, V, H = ro::DefaultHashBuilder> {
+    field: &'a F,
+    store: &'a mut S,
+    views: &'a mut LiveViews<S, F, V>,
+    generation: &'a mut usize,
+    entry: ro::VacantEntry<'a, K, V, H>,
+}
+
+impl<'a, S, F, K, V, H> VacantEntry<'a, S, F, K, V, H>
+where
+    S: Store<Pos = K>,
+    F: Field<S, V>,
+    K: Hash + Eq + Clone,
+    H: BuildHasher,
+{
+    /// Gets a reference to the key that would be used when inserting a value through the `VacantEntry`.
+    pub fn key(&self) -> &K {
+        self.entry.key()
+    }
+
+    /// Sets the value of the entry and returns a reference to it.
+    pub fn insert(self, item: V) -> &'a V {
+        self.field
+            .insert(self.store, &item, self.entry.key().clone());
+        if let Some(key) = self.views.key(self.field, &item) {
+            self.views.insert(&key, self.entry.key());
+        }
+        *self.generation = self.generation.wrapping_add(1);
+        self.entry.insert(item)
     }
 }
 
@@ -175,6 +742,224 @@ mod tests {
         assert_eq!(0, m.len());
     }
 
+    #[test]
+    fn remove_by_many_keys() {
+        let mut m = LkupHashMap::new(IndexLookup::with_multi_keys(), |c: &Car| c.0);
+        m.insert(String::from("Audi"), Car(1, String::from("Audi")));
+        m.insert(String::from("BMW"), Car(2, String::from("BMW")));
+        m.insert(String::from("VW"), Car(1, String::from("VW")));
+        m.insert(String::from("Opel"), Car(3, String::from("Opel")));
+
+        assert_eq!(2, m.remove_by_key(1));
+        assert!(!m.contains_lkup_key(1));
+        assert_eq!(2, m.len());
+
+        assert_eq!(2, m.remove_by_many_keys([3, 2, 3, 1, 99]));
+        assert!(m.is_empty());
+        assert!(m.lkup_ext().keys().next().is_none());
+    }
+
+    #[test]
+    fn borrowed_key() {
+        use crate::Borrowed;
+
+        let mut m = LkupHashMap::new(
+            HashLookup::with_unique_key(),
+            Borrowed::new(|c: &Car| c.1.as_str()),
+        );
+        m.insert(1, Car(1, String::from("BMW")));
+        m.insert(2, Car(2, String::from("Audi")));
+        assert!(m.contains_lkup_key("BMW"));
+
+        // replace item
+        m.insert(1, Car(1, String::from("VW")));
+        assert!(!m.contains_lkup_key("BMW"));
+        assert!(m.contains_lkup_key("VW"));
+
+        m.update(1, |c| c.0 = 5);
+        assert_eq!(
+            Some(&Car(5, String::from("VW"))),
+            m.get_by_lkup_key("VW").next()
+        );
+
+        m.entry(2).and_modify(|c| c.1 = String::from("Opel"));
+        assert!(!m.contains_lkup_key("Audi"));
+        assert!(m.contains_lkup_key("Opel"));
+
+        assert_eq!(Some(Car(5, String::from("VW"))), m.remove(1));
+        assert!(!m.contains_lkup_key("VW"));
+    }
+
+    #[test]
+    fn entry() {
+        let mut m = LkupHashMap::new(IndexLookup::with_multi_keys(), |c: &Car| c.0);
+
+        // vacant
+        assert_eq!("Audi", m.entry(String::from("Audi")).key());
+        assert_eq!(
+            &Car(99, String::from("Audi")),
+            m.entry(String::from("Audi"))
+                .or_insert(Car(99, String::from("Audi")))
+        );
+        assert!(m.contains_lkup_key(99));
+
+        // occupied, no insert
+        assert_eq!(
+            &Car(99, String::from("Audi")),
+            m.entry(String::from("Audi"))
+                .or_insert_with(|| Car(1, String::from("BMW")))
+        );
+        assert!(!m.contains_lkup_key(1));
+
+        // modify with changing the lookup-key
+        assert_eq!(
+            &Car(5, String::from("Audi")),
+            m.entry(String::from("Audi"))
+                .and_modify(|c| c.0 = 5)
+                .or_insert(Car(1, String::from("BMW")))
+        );
+        assert!(!m.contains_lkup_key(99));
+        assert_eq!(
+            vec![&Car(5, String::from("Audi"))],
+            m.get_by_lkup_key(5).collect::<Vec<_>>()
+        );
+
+        // modify on vacant is ignored
+        m.entry(String::from("BMW"))
+            .and_modify(|c| c.0 = 7)
+            .or_insert_with(|| Car(1, String::from("BMW")));
+        assert!(m.contains_lkup_key(1));
+        assert!(!m.contains_lkup_key(7));
+        assert_eq!(2, m.len());
+
+        // remove by occupied entry
+        match m.entry(String::from("BMW")) {
+            Entry::Occupied(e) => assert_eq!(Car(1, String::from("BMW")), e.remove()),
+            Entry::Vacant(_) => unreachable!("BMW must exist"),
+        }
+        assert!(!m.contains_lkup_key(1));
+        assert_eq!(1, m.len());
+    }
+
+    #[test]
+    fn from_iter_extend_and_into_iter() {
+        let mut m = LkupHashMap::from_iter(
+            HashLookup::with_unique_key(),
+            |c: &Car| c.1.clone(),
+            [(1, Car(1, String::from("BMW")))],
+        );
+        assert!(m.contains_lkup_key("BMW"));
+
+        m.extend([
+            (99, Car(99, String::from("Audi"))),
+            (1, Car(1, String::from("VW"))),
+        ]);
+        assert_eq!(2, m.len());
+        assert!(m.contains_lkup_key("Audi"));
+        // the old item with key 1 is replaced
+        assert!(!m.contains_lkup_key("BMW"));
+        assert!(m.contains_lkup_key("VW"));
+
+        // equal items with a different lookup
+        let mut other = LkupHashMap::new(IndexLookup::with_unique_key(), |c: &Car| c.0);
+        other.extend(m.iter().map(|(k, c)| (*k, Car(c.0, c.1.clone()))));
+        assert!(m == other);
+
+        other.remove(1);
+        assert!(m != other);
+
+        let mut items = m.into_iter().map(|(k, _)| k).collect::<Vec<_>>();
+        items.sort();
+        assert_eq!(vec![1, 99], items);
+    }
+
+    #[test]
+    fn with_hasher() {
+        use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+        let mut map = LkupHashMap::with_hasher(
+            HashLookup::with_multi_keys()
+                .with_hasher(BuildHasherDefault::<DefaultHasher>::default()),
+            |c: &Car| c.1.clone(),
+            BuildHasherDefault::<DefaultHasher>::default(),
+        );
+
+        map.insert(1, Car(1, "BMW".into()));
+        map.entry(2).or_insert(Car(2, "Audi".into()));
+        map.entry(3).or_insert(Car(3, "BMW".into()));
+        map.update(3, |c| c.1 = "VW".into());
+
+        assert_eq!(
+            vec![&Car(1, "BMW".into())],
+            map.get_by_lkup_key("BMW").collect::<Vec<_>>()
+        );
+        assert!(map.contains_lkup_key("VW"));
+
+        let view = map.create_lkup_view([String::from("Audi")]);
+        assert_eq!(
+            vec![&Car(2, "Audi".into())],
+            view.items().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn views_with_seeded_hasher() {
+        use std::{
+            collections::hash_map::DefaultHasher,
+            hash::{BuildHasher, Hasher},
+        };
+
+        #[derive(Default, Clone, Debug, PartialEq)]
+        struct Seeded(u64);
+
+        impl BuildHasher for Seeded {
+            type Hasher = DefaultHasher;
+
+            fn build_hasher(&self) -> Self::Hasher {
+                let mut h = DefaultHasher::new();
+                h.write_u64(self.0);
+                h
+            }
+        }
+
+        let mut map = LkupHashMap::new(
+            HashLookup::with_multi_keys().with_hasher(Seeded(42)),
+            |c: &Car| c.1.clone(),
+        );
+        map.insert(1, Car(1, "BMW".into()));
+
+        let owned = map.create_owned_lkup_view([String::from("BMW")]);
+        assert_eq!(&Seeded(42), owned.hasher());
+
+        let handle = map.register_view([String::from("BMW")]);
+        map.insert(2, Car(2, "BMW".into()));
+        let live = map.live_view(handle).unwrap();
+        assert_eq!(&Seeded(42), live.hasher());
+        assert_eq!(2, live.get_by_key("BMW").count());
+    }
+
+    #[test]
+    fn into_ro_and_into_rw() {
+        let mut map = LkupHashMap::new(HashLookup::with_multi_keys(), |c: &Car| c.1.clone());
+        map.insert(1, Car(1, "BMW".into()));
+        map.insert(2, Car(2, "Audi".into()));
+
+        let ro = map.into_ro();
+        assert!(ro.contains_lkup_key("BMW"));
+
+        let mut map = ro.into_rw(|c: &Car| c.1.clone());
+        map.insert(3, Car(3, "BMW".into()));
+        map.remove(1);
+        assert_eq!(
+            vec![&Car(3, "BMW".into())],
+            map.get_by_lkup_key("BMW").collect::<Vec<_>>()
+        );
+
+        let map = map.rebuild_store_with(IndexLookup::with_unique_key(), |c: &Car| c.0 as usize);
+        assert!(map.contains_lkup_key(2));
+        assert!(!map.contains_lkup_key(1));
+    }
+
     #[test]
     fn map_key_usize() {
         let mut m = LkupHashMap::new(HashLookup::with_unique_key(), |c: &Car| c.1.clone());
@@ -208,4 +993,124 @@ mod tests {
         assert!(!m.contains_lkup_key("VW"));
         assert_eq!(1, m.len());
     }
+
+    #[test]
+    fn live_view() {
+        let mut map = LkupHashMap::new(IndexLookup::with_multi_keys(), |c: &Car| c.0);
+        map.insert(String::from("BMW"), Car(1, "BMW".into()));
+        map.insert(String::from("Audi"), Car(2, "Audi".into()));
+
+        let handle = map.register_view([1]);
+        map.insert(String::from("VW"), Car(1, "VW".into()));
+        map.entry(String::from("Opel"))
+            .or_insert(Car(1, "Opel".into()));
+
+        let view = map.live_view(handle).unwrap();
+        assert_eq!(3, view.get_by_key(1).count());
+        assert!(!view.contains_key(2));
+
+        map.update(String::from("VW"), |c| c.0 = 2);
+        map.remove(String::from("BMW"));
+        map.entry(String::from("Audi")).and_modify(|c| c.0 = 1);
+
+        let view = map.live_view(handle).unwrap();
+        let mut cars = view.get_by_key(1).map(|c| c.1.as_str()).collect::<Vec<_>>();
+        cars.sort();
+        assert_eq!(vec!["Audi", "Opel"], cars);
+    }
+
+    #[test]
+    fn for_each_mut_by_key_and_remove_view() {
+        let mut map = LkupHashMap::new(IndexLookup::with_multi_keys(), |c: &Car| c.0);
+        map.insert(String::from("BMW"), Car(1, "BMW".into()));
+        map.insert(String::from("Audi"), Car(2, "Audi".into()));
+        map.insert(String::from("VW"), Car(1, "VW".into()));
+
+        map.for_each_mut_by_key([1, 2], |key, c| c.0 = *key * 10);
+        assert!(!map.contains_lkup_key(1));
+        assert_eq!(2, map.get_by_lkup_key(10).count());
+        assert_eq!(
+            vec![&Car(20, "Audi".into())],
+            map.get_by_lkup_key(20).collect::<Vec<_>>()
+        );
+
+        assert_eq!(3, map.remove_view([10, 20, 10]));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn for_each_mut_by_key_change_to_following_key() {
+        let mut map = LkupHashMap::new(IndexLookup::with_multi_keys(), |c: &Car| c.0);
+        map.insert(String::from("BMW"), Car(1, "BMW".into()));
+        map.insert(String::from("Audi"), Car(2, "Audi".into()));
+        map.insert(String::from("VW"), Car(1, "VW".into()));
+        let handle = map.register_view([2]);
+
+        // the Items with key 1 are changed to 2, but not updated again by the key 2
+        let mut calls = 0;
+        map.for_each_mut_by_key([1, 2, 1], |key, c| {
+            calls += 1;
+            c.0 = *key + 1;
+        });
+
+        assert_eq!(3, calls);
+        assert!(!map.contains_lkup_key(1));
+        assert_eq!(2, map.get_by_lkup_key(2).count());
+        assert_eq!(
+            vec![&Car(3, "Audi".into())],
+            map.get_by_lkup_key(3).collect::<Vec<_>>()
+        );
+
+        let view = map.live_view(handle).unwrap();
+        let mut names = view.items().map(|c| c.1.as_str()).collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(vec!["BMW", "VW"], names);
+    }
+
+    #[test]
+    fn update_view_with_duplicate_keys() {
+        let mut map = LkupHashMap::new(IndexLookup::with_multi_keys(), |c: &Car| c.0);
+        map.insert(String::from("BMW"), Car(1, "BMW".into()));
+        map.insert(String::from("Audi"), Car(2, "Audi".into()));
+        map.insert(String::from("VW"), Car(1, "VW".into()));
+        let handle = map.register_view([9]);
+
+        assert_eq!(3, map.update_view([1, 2, 1], |c| c.0 = 9));
+        assert_eq!(0, map.update_view([1, 7], |c| c.0 = 0));
+        assert!(!map.contains_lkup_key(1));
+        assert_eq!(3, map.get_by_lkup_key(9).count());
+        assert_eq!(3, map.live_view(handle).unwrap().items().count());
+    }
+
+    #[test]
+    fn owned_view_refresh_after_remove() {
+        let mut m = LkupHashMap::new(HashLookup::with_multi_keys(), |c: &Car| c.1.clone());
+        m.insert(1, Car(1, String::from("BMW")));
+        m.insert(2, Car(2, String::from("Audi")));
+        m.insert(3, Car(3, String::from("BMW")));
+
+        let mut view = m.create_owned_lkup_view([String::from("BMW")]);
+        assert_eq!(2, m.with_owned_view(&view).unwrap().items().count());
+
+        m.remove(1);
+        assert!(m.with_owned_view(&view).is_none());
+
+        m.refresh_owned_view(&mut view);
+        assert_eq!(
+            vec![&Car(3, String::from("BMW"))],
+            m.with_owned_view(&view)
+                .unwrap()
+                .items()
+                .collect::<Vec<_>>()
+        );
+
+        // modified by an Entry
+        m.entry(4).or_insert(Car(4, String::from("BMW")));
+        assert!(m.with_owned_view(&view).is_none());
+        m.refresh_owned_view(&mut view);
+
+        // an existing Entry without modification
+        m.entry(4).or_insert(Car(5, String::from("VW")));
+        assert_eq!(2, m.with_owned_view(&view).unwrap().items().count());
+    }
 }
@@ -1,11 +1,17 @@
 //! The `collections` module contains the collections implementations which are using the lookups.
 //!
 
+pub mod aggregate;
+pub mod join;
 pub mod list;
+pub mod live;
 pub mod map;
 
-use crate::lookup::store::{self, Positions, Retriever};
-use std::ops::Index;
+use crate::{
+    collections::aggregate::Aggregator,
+    lookup::store::{self, Groups, Positions, Retriever},
+};
+use std::{cmp::Ordering, hash::Hash, ops::Index};
 
 pub use crate::collections::list::rw::LkupVec;
 pub use crate::collections::map::rw::LkupHashMap;
@@ -111,6 +117,64 @@ impl<R, I> View<R, I> {
         self.view.pos_by_many_keys(keys).map(|p| &self.items[p])
     }
 
+    /// Like [`View::get_by_many_keys`], but every `Item` is returned only once
+    /// (in the order of the first occurrence).
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, IndexLookup, Lookup};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, String);
+    ///
+    /// let cars = [Car(5, "BMW".into()), Car(1, "Audi".into()), Car(3, "VW".into())];
+    ///
+    /// let v = LkupList::new(IndexLookup::with_multi_keys(), |c| c.0, cars);
+    /// let view = v.create_lkup_view([1, 5]);
+    ///
+    /// assert_eq!(
+    ///     vec![&Car(5, "BMW".into()), &Car(1, "Audi".into())],
+    ///     view.get_by_unique_many_keys([5, 1, 5]).collect::<Vec<_>>()
+    /// );
+    /// assert_eq!(
+    ///     vec![&Car(5, "BMW".into()), &Car(1, "Audi".into())],
+    ///     view.get_by_unique_many_keys_sorted([1, 5, 1]).collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn get_by_unique_many_keys<'a, It, Q>(
+        &'a self,
+        keys: It,
+    ) -> impl Iterator<Item = &'a I::Output>
+    where
+        It: IntoIterator<Item = Q> + 'a,
+        I: Index<&'a R::Pos>,
+        R: Retriever<Q>,
+        R::Pos: Hash + Eq,
+        Q: 'a,
+    {
+        self.view
+            .pos_by_many_keys_dedup(keys)
+            .map(|p| &self.items[p])
+    }
+
+    /// Like [`View::get_by_unique_many_keys`], but the `Item`s are ordered by the position.
+    pub fn get_by_unique_many_keys_sorted<'a, It, Q>(
+        &'a self,
+        keys: It,
+    ) -> impl Iterator<Item = &'a I::Output>
+    where
+        It: IntoIterator<Item = Q> + 'a,
+        I: Index<&'a R::Pos>,
+        R: Retriever<Q>,
+        R::Pos: Ord,
+        Q: 'a,
+    {
+        self.view
+            .pos_by_many_keys_sorted(keys)
+            .map(|p| &self.items[p])
+    }
+
     /// Return all items for the given `View`.
     ///
     /// # Example:
@@ -139,6 +203,264 @@ impl<R, I> View<R, I> {
     {
         self.view.positions().map(|p| &self.items[p])
     }
+
+    /// Return all items for the given `View`, sorted by the given compare function.
+    /// The sort is stable, so `Item`s, which are equal, keep the order from [`View::items`].
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, HashLookup, Lookup};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, String);
+    ///
+    /// let cars = [Car(5, "BMW".into()), Car(1, "Audi".into()), Car(3, "VW".into())];
+    ///
+    /// let v = LkupList::new(HashLookup::with_multi_keys(), |c| c.0, cars);
+    /// let view = v.create_lkup_view([1, 5]);
+    ///
+    /// assert_eq!(
+    ///     vec![&Car(1, "Audi".into()), &Car(5, "BMW".into())],
+    ///     view.items_sorted_by(|a, b| a.1.cmp(&b.1)).collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn items_sorted_by<'a, F>(&'a self, mut compare: F) -> impl Iterator<Item = &'a I::Output>
+    where
+        I: Index<&'a R::Pos>,
+        R: Positions,
+        F: FnMut(&I::Output, &I::Output) -> Ordering,
+    {
+        let mut items = self.items().collect::<Vec<_>>();
+        items.sort_by(|a, b| compare(a, b));
+        items.into_iter()
+    }
+
+    /// Return all items for the given `View`, sorted by the `Key`s from the `Lookup`.
+    /// The `Item`s with the same `Key` are in the order of the `Store`.
+    ///
+    /// Ordered `Store`s (e.g. `IndexLookup`, `SortedLookup`) return the `Key`s already sorted,
+    /// for all other `Store`s (e.g. `HashLookup`) the `Key`s are sorted.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, HashLookup, Lookup};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, String);
+    ///
+    /// let cars = [Car(5, "BMW".into()), Car(1, "Audi".into()), Car(3, "VW".into())];
+    ///
+    /// let v = LkupList::new(HashLookup::with_multi_keys(), |c| c.0, cars);
+    /// let view = v.create_lkup_view([5, 3, 1]);
+    ///
+    /// assert_eq!(
+    ///     vec![&Car(1, "Audi".into()), &Car(3, "VW".into()), &Car(5, "BMW".into())],
+    ///     view.items_sorted_by_key().collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn items_sorted_by_key<'a>(&'a self) -> impl Iterator<Item = &'a I::Output>
+    where
+        I: Index<&'a R::Pos>,
+        R: Groups,
+        R::Key: Ord,
+    {
+        let mut groups = self.view.groups().collect::<Vec<_>>();
+        // sort is fast, if the groups are already sorted
+        groups.sort_by_key(|(key, _)| *key);
+        groups
+            .into_iter()
+            .flat_map(|(_, pos)| pos.iter().map(|p| &self.items[p]))
+    }
+
+    /// Return a page of the `Item`s for the given `View`, for example for a paginated table.
+    /// The `Item`s are sorted by the `Key`s (see [`View::items_sorted_by_key`]),
+    /// so the order is stable between the pages.
+    ///
+    /// Every call collects and sorts the `Key`s of the `View` again,
+    /// for iterating over all pages use [`View::pages`], which sorts the `Key`s only once.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, HashLookup, Lookup};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, String);
+    ///
+    /// let cars = [Car(5, "BMW".into()), Car(1, "Audi".into()), Car(3, "VW".into())];
+    ///
+    /// let v = LkupList::new(HashLookup::with_multi_keys(), |c| c.0, cars);
+    /// let view = v.create_lkup_view([5, 3, 1]);
+    ///
+    /// assert_eq!(vec![&Car(1, "Audi".into()), &Car(3, "VW".into())], view.page(0, 2).collect::<Vec<_>>());
+    /// assert_eq!(vec![&Car(5, "BMW".into())], view.page(2, 2).collect::<Vec<_>>());
+    /// ```
+    pub fn page<'a>(&'a self, offset: usize, limit: usize) -> impl Iterator<Item = &'a I::Output>
+    where
+        I: Index<&'a R::Pos>,
+        R: Groups,
+        R::Key: Ord,
+    {
+        self.items_sorted_by_key().skip(offset).take(limit)
+    }
+
+    /// Return all pages with maximal `limit` `Item`s for the given `View`.
+    /// The `Item`s are sorted by the `Key`s only once (see [`View::items_sorted_by_key`]).
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, HashLookup, Lookup};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, String);
+    ///
+    /// let cars = [Car(5, "BMW".into()), Car(1, "Audi".into()), Car(3, "VW".into())];
+    ///
+    /// let v = LkupList::new(HashLookup::with_multi_keys(), |c| c.0, cars);
+    /// let view = v.create_lkup_view([5, 3, 1]);
+    ///
+    /// let mut pages = view.pages(2);
+    /// assert_eq!(Some(vec![&Car(1, "Audi".into()), &Car(3, "VW".into())]), pages.next());
+    /// assert_eq!(Some(vec![&Car(5, "BMW".into())]), pages.next());
+    /// assert_eq!(None, pages.next());
+    /// ```
+    pub fn pages<'a>(&'a self, limit: usize) -> impl Iterator<Item = Vec<&'a I::Output>>
+    where
+        I: Index<&'a R::Pos>,
+        R: Groups,
+        R::Key: Ord,
+    {
+        let mut items = self.items_sorted_by_key();
+        std::iter::from_fn(move || {
+            let page = items.by_ref().take(limit).collect::<Vec<_>>();
+            (!page.is_empty()).then_some(page)
+        })
+    }
+
+    /// Return all `Key`s of the given `View` with the associated `Item`s.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, IndexLookup, Lookup};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, String);
+    ///
+    /// let cars = [Car(5, "BMW".into()), Car(1, "Audi".into()), Car(5, "VW".into())];
+    ///
+    /// let v = LkupList::new(IndexLookup::with_multi_keys(), |c| c.0, cars);
+    /// let view = v.create_lkup_view([5]);
+    ///
+    /// let (key, cars) = view.group_by_key().next().unwrap();
+    /// assert_eq!(&5, key);
+    /// assert_eq!(vec![&Car(5, "BMW".into()), &Car(5, "VW".into())], cars.collect::<Vec<_>>());
+    /// ```
+    pub fn group_by_key<'a>(
+        &'a self,
+    ) -> impl Iterator<Item = (&'a R::Key, impl Iterator<Item = &'a I::Output>)>
+    where
+        I: Index<&'a R::Pos>,
+        R: Groups,
+    {
+        self.view
+            .groups()
+            .map(|(key, pos)| (key, pos.iter().map(|p| &self.items[p])))
+    }
+
+    /// Returns the count of `Item`s for the given `Key`.
+    pub fn count_by_key<Q>(&self, key: Q) -> usize
+    where
+        R: Retriever<Q>,
+    {
+        self.view.pos_by_key(key).len()
+    }
+
+    /// Returns the count of `Item`s for all given `Key`s.
+    pub fn count_by_many_keys<It, Q>(&self, keys: It) -> usize
+    where
+        R: Retriever<Q>,
+        It: IntoIterator<Item = Q>,
+    {
+        self.view.count_by_many_keys(keys)
+    }
+
+    /// Returns the count of the given `Key`s, which exist in the `View`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use lookups::{collections::list::ro::LkupList, IndexLookup, Lookup};
+    ///
+    /// let v = LkupList::new(IndexLookup::with_multi_keys(), |n: &usize| *n, [1, 3, 1, 5]);
+    /// let view = v.create_lkup_view([1, 5]);
+    ///
+    /// assert_eq!(1, view.count_existing_keys([1, 3]));
+    /// assert_eq!(vec![3], view.missing_keys([1, 3]).collect::<Vec<_>>());
+    /// assert_eq!(3, view.count_by_many_keys([1, 3, 5]));
+    /// ```
+    pub fn count_existing_keys<It, Q>(&self, keys: It) -> usize
+    where
+        R: Retriever<Q>,
+        It: IntoIterator<Item = Q>,
+    {
+        self.view.count_existing_keys(keys)
+    }
+
+    /// Returns all given `Key`s, which not exist in the `View`.
+    pub fn missing_keys<'a, It, Q>(&'a self, keys: It) -> impl Iterator<Item = Q> + 'a
+    where
+        R: Retriever<Q>,
+        It: IntoIterator<Item = Q> + 'a,
+        Q: Clone + 'a,
+    {
+        self.view.missing_keys(keys)
+    }
+
+    /// Returns all `Key`s of the given `View` with the count of the associated `Item`s.
+    pub fn histogram(&self) -> impl Iterator<Item = (&R::Key, usize)>
+    where
+        R: Groups,
+    {
+        self.view.groups().map(|(key, pos)| (key, pos.len()))
+    }
+
+    /// Returns all `Key`s of the given `View` with the aggregated `value`s of the associated `Item`s.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use lookups::{collections::{aggregate::Max, list::ro::LkupList}, IndexLookup, Lookup};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, u32);
+    ///
+    /// let cars = [Car(5, 200), Car(1, 180), Car(5, 250)];
+    ///
+    /// let v = LkupList::new(IndexLookup::with_multi_keys(), |c| c.0, cars);
+    /// let view = v.create_lkup_view([5]);
+    ///
+    /// assert_eq!(vec![(&5, Some(250))], view.aggregate_by_key(|c| c.1, Max).collect::<Vec<_>>());
+    /// ```
+    pub fn aggregate_by_key<'a, F, T, A>(
+        &'a self,
+        value: F,
+        aggregator: A,
+    ) -> impl Iterator<Item = (&'a R::Key, A::Output)> + 'a
+    where
+        I: Index<&'a R::Pos>,
+        R: Groups,
+        F: Fn(&'a I::Output) -> T + 'a,
+        A: Aggregator<T> + 'a,
+    {
+        self.view.groups().map(move |(key, pos)| {
+            let values = pos.iter().map(|p| value(&self.items[p]));
+            (key, aggregator.aggregate(values))
+        })
+    }
 }
 
 impl<L, I> std::ops::Deref for View<L, I>
@@ -151,3 +473,137 @@ where
         self.view.deref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{collections::list::ro::LkupList, HashLookup, IndexLookup, InternLookup, Lookup};
+
+    #[derive(Debug, PartialEq)]
+    struct Car(usize, String);
+
+    fn cars() -> Vec<Car> {
+        vec![
+            Car(5, "BMW".into()),
+            Car(1, "Audi".into()),
+            Car(5, "VW".into()),
+            Car(3, "Audi".into()),
+            Car(1, "Opel".into()),
+        ]
+    }
+
+    fn car(id: usize, name: &str) -> Car {
+        Car(id, name.into())
+    }
+
+    #[test]
+    fn sorted_and_pages_index_store() {
+        let v = LkupList::new(IndexLookup::with_multi_keys(), |c: &Car| c.0, cars());
+        let view = v.create_lkup_view([5, 1]);
+
+        // the Items with the same Key are in the order of the Store
+        assert_eq!(
+            vec![
+                &car(1, "Audi"),
+                &car(1, "Opel"),
+                &car(5, "BMW"),
+                &car(5, "VW")
+            ],
+            view.items_sorted_by_key().collect::<Vec<_>>()
+        );
+        // stable sort: the Items with the same id keep the order from items
+        assert_eq!(
+            vec![
+                &car(5, "BMW"),
+                &car(5, "VW"),
+                &car(1, "Audi"),
+                &car(1, "Opel")
+            ],
+            view.items_sorted_by(|a, b| b.0.cmp(&a.0))
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![&car(1, "Opel"), &car(5, "BMW")],
+            view.page(1, 2).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![&car(5, "VW")], view.page(3, 10).collect::<Vec<_>>());
+        // offset out of range
+        assert_eq!(0, view.page(4, 2).count());
+        assert_eq!(0, view.page(100, 2).count());
+        assert_eq!(0, view.page(0, 0).count());
+
+        assert_eq!(
+            vec![
+                vec![&car(1, "Audi"), &car(1, "Opel"), &car(5, "BMW")],
+                vec![&car(5, "VW")]
+            ],
+            view.pages(3).collect::<Vec<_>>()
+        );
+        assert_eq!(0, view.pages(0).count());
+    }
+
+    #[test]
+    fn sorted_and_pages_hash_store() {
+        let v = LkupList::new(HashLookup::with_multi_keys(), |c: &Car| c.1.clone(), cars());
+        let view = v.create_lkup_view(["Audi".into(), "VW".into(), "Opel".into()]);
+
+        assert_eq!(
+            vec![
+                &car(1, "Audi"),
+                &car(3, "Audi"),
+                &car(1, "Opel"),
+                &car(5, "VW")
+            ],
+            view.items_sorted_by_key().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                &car(5, "VW"),
+                &car(3, "Audi"),
+                &car(1, "Audi"),
+                &car(1, "Opel")
+            ],
+            view.items_sorted_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)))
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![&car(3, "Audi"), &car(1, "Opel")],
+            view.page(1, 2).collect::<Vec<_>>()
+        );
+        assert_eq!(0, view.page(4, 2).count());
+        assert_eq!(2, view.pages(2).count());
+    }
+
+    #[test]
+    fn sorted_and_pages_intern_store() {
+        let v = LkupList::new(
+            InternLookup::with_multi_keys(),
+            |c: &Car| c.1.clone(),
+            cars(),
+        );
+        let view = v.create_lkup_view(["VW".into(), "Audi".into(), "BMW".into()]);
+
+        assert_eq!(
+            vec![
+                &car(1, "Audi"),
+                &car(3, "Audi"),
+                &car(5, "BMW"),
+                &car(5, "VW")
+            ],
+            view.items_sorted_by_key().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![&car(5, "BMW"), &car(5, "VW")],
+            view.page(2, 5).collect::<Vec<_>>()
+        );
+        assert_eq!(0, view.page(4, 1).count());
+        assert_eq!(
+            vec![
+                vec![&car(1, "Audi"), &car(3, "Audi")],
+                vec![&car(5, "BMW"), &car(5, "VW")]
+            ],
+            view.pages(2).collect::<Vec<_>>()
+        );
+    }
+}
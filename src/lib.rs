@@ -61,7 +61,11 @@ pub mod lookup;
 pub use collections::list::rw::LkupVec;
 pub use collections::map::rw::LkupHashMap;
 
+pub use lookup::field::Borrowed;
+pub use lookup::frozen::FrozenHashLookup;
 pub use lookup::hash::HashLookup;
 pub use lookup::index::IndexLookup;
+pub use lookup::intern::InternLookup;
+pub use lookup::sorted::SortedLookup;
 
 pub use lookup::store::Lookup;
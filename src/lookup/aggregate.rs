@@ -0,0 +1,468 @@
+//! The `aggregate` module contains a hashing lookup, which maintains for every `Key` the aggregates
+//! (count, sum, min, max) of a value from the associated `Item`s.
+//!
+//! The `Key` for the `Store` is a pair: `(Key, Value)`, e.g. `(customer_id, amount)`.
+//! The aggregates are updated by every write operation (insert, update, delete),
+//! so they are never recomputed.
+//!
+//! # Example
+//!
+//! ```
+//! use lookups::{lookup::aggregate::AggregateLookup, LkupVec, Lookup};
+//!
+//! struct Order {
+//!     customer_id: usize,
+//!     amount: u32,
+//! }
+//!
+//! let mut orders = LkupVec::new(AggregateLookup::with_multi_keys(), |o: &Order| (o.customer_id, o.amount));
+//! orders.push(Order{customer_id: 1, amount: 10});
+//! orders.push(Order{customer_id: 2, amount: 5});
+//! orders.push(Order{customer_id: 1, amount: 7});
+//!
+//! assert_eq!(Some(&17), orders.lkup_ext().sum(&1));
+//!
+//! orders.update(2, |o| o.amount = 2);
+//! assert_eq!(Some(&12), orders.lkup_ext().sum(&1));
+//! assert_eq!(Some(&2), orders.lkup_ext().min(&1));
+//!
+//! orders.remove(0);
+//! assert_eq!(Some(1), orders.lkup_ext().count(&1));
+//! assert_eq!(Some(&2), orders.lkup_ext().sum(&1));
+//! ```
+//!
+#[cfg(feature = "rayon")]
+use crate::lookup::store::{position::MergeKeyPosition, MergeStore};
+use crate::lookup::store::{
+    position::{KeyPosition, KeyPositionAsSlice},
+    Groups, Lookup, Positions, Retriever, Store,
+};
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    collections::BTreeMap,
+    hash::Hash,
+    marker::PhantomData,
+    ops::{AddAssign, Deref, SubAssign},
+};
+
+#[cfg(feature = "hashbrown")]
+type HashMap<K, V> = hashbrown::HashMap<K, V>;
+
+#[cfg(not(feature = "hashbrown"))]
+type HashMap<K, V> = std::collections::HashMap<K, V>;
+
+/// `AggregateLookup` is the creator for the `AggregateStore`: `Retriever` and `Store`.
+pub struct AggregateLookup<K, V, P>(PhantomData<K>, PhantomData<V>, PhantomData<P>);
+
+impl<K, V, P> Lookup<AggregateStore<K, V, P>, P> for AggregateLookup<K, V, P>
+where
+    K: Hash + Eq,
+    V: PartialOrd + Clone + Default + AddAssign + SubAssign,
+    P: KeyPosition + KeyPositionAsSlice,
+{
+    fn new() -> Self {
+        Self(PhantomData, PhantomData, PhantomData)
+    }
+}
+
+/// The aggregates of all values for one `Key`.
+///
+/// The values must be only [`PartialOrd`] (e.g. `f64`), values which are not comparable (like `NaN`)
+/// are greater than all other values (a `NaN` value makes the sum `NaN`).
+#[derive(Debug, Clone)]
+pub struct Aggregate<V> {
+    count: usize,
+    sum: V,
+    // multiset of all values, for min and max
+    values: BTreeMap<TotalOrder<V>, usize>,
+}
+
+// a total order for `PartialOrd` values, the not comparable values (like `NaN`) are the greatest values
+#[derive(Debug, Clone)]
+struct TotalOrder<V>(V);
+
+impl<V: PartialOrd> TotalOrder<V> {
+    fn is_comparable(&self) -> bool {
+        self.0.partial_cmp(&self.0).is_some()
+    }
+}
+
+impl<V: PartialOrd> Ord for TotalOrder<V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .partial_cmp(&other.0)
+            .unwrap_or_else(|| other.is_comparable().cmp(&self.is_comparable()))
+    }
+}
+
+impl<V: PartialOrd> PartialOrd for TotalOrder<V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<V: PartialOrd> PartialEq for TotalOrder<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<V: PartialOrd> Eq for TotalOrder<V> {}
+
+impl<V> Aggregate<V>
+where
+    V: PartialOrd + Clone + Default + AddAssign + SubAssign,
+{
+    fn new() -> Self {
+        Self {
+            count: 0,
+            sum: V::default(),
+            values: BTreeMap::new(),
+        }
+    }
+
+    fn add(&mut self, value: V) {
+        self.count += 1;
+        self.sum += value.clone();
+        *self.values.entry(TotalOrder(value)).or_default() += 1;
+    }
+
+    fn remove(&mut self, value: V) {
+        let value = TotalOrder(value);
+        if let Some(n) = self.values.get_mut(&value) {
+            *n -= 1;
+            if *n == 0 {
+                self.values.remove(&value);
+            }
+            self.count -= 1;
+            self.sum -= value.0;
+        }
+    }
+}
+
+impl<V> Aggregate<V> {
+    /// The count of values.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The sum of all values.
+    pub fn sum(&self) -> &V {
+        &self.sum
+    }
+
+    /// The smallest value.
+    pub fn min(&self) -> Option<&V> {
+        self.values.keys().next().map(|v| &v.0)
+    }
+
+    /// The greatest value.
+    pub fn max(&self) -> Option<&V> {
+        self.values.keys().next_back().map(|v| &v.0)
+    }
+}
+
+/// `AggregateStore` is a hash index, which maintains the [`Aggregate`]s for every `Key`.
+///
+#[derive(Debug, Clone)]
+#[repr(transparent)]
+pub struct AggregateStore<K, V, P>(HashMap<K, (P, Aggregate<V>)>);
+
+impl<Q, K, V, P> Retriever<&Q> for AggregateStore<K, V, P>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: Hash + Eq + ?Sized,
+    P: KeyPositionAsSlice,
+{
+    type Pos = P::Pos;
+
+    fn key_exist(&self, key: &Q) -> bool {
+        self.0.contains_key(key)
+    }
+
+    fn pos_by_key(&self, key: &Q) -> &[Self::Pos] {
+        match self.0.get(key) {
+            Some((p, _)) => p.as_position_slice(),
+            None => &[],
+        }
+    }
+}
+
+impl<K, V, P> Positions for AggregateStore<K, V, P>
+where
+    P: KeyPositionAsSlice,
+{
+    type Pos = P::Pos;
+
+    fn positions(&self) -> impl Iterator<Item = &'_ P::Pos> {
+        self.0.values().flat_map(|(p, _)| p.as_position_slice())
+    }
+}
+
+impl<K, V, P> Groups for AggregateStore<K, V, P>
+where
+    P: KeyPositionAsSlice,
+{
+    type Key = K;
+    type Pos = P::Pos;
+
+    fn groups(&self) -> impl Iterator<Item = (&'_ K, &'_ [P::Pos])> {
+        self.0.iter().map(|(k, (p, _))| (k, p.as_position_slice()))
+    }
+}
+
+impl<K, V, P> Store for AggregateStore<K, V, P>
+where
+    K: Hash + Eq,
+    V: PartialOrd + Clone + Default + AddAssign + SubAssign,
+    P: KeyPosition + KeyPositionAsSlice,
+{
+    type Key = (K, V);
+    type Pos = <P as KeyPosition>::Pos;
+
+    fn insert(&mut self, (key, value): Self::Key, pos: Self::Pos) {
+        match self.0.get_mut(&key) {
+            Some((p, aggregate)) => {
+                p.add_pos(pos);
+                aggregate.add(value);
+            }
+            None => {
+                let mut aggregate = Aggregate::new();
+                aggregate.add(value);
+                self.0.insert(key, (P::from_pos(pos), aggregate));
+            }
+        }
+    }
+
+    /// If the `Key` and the value are not changed, the `Store` is not touched.
+    fn update(&mut self, old_key: Self::Key, pos: Self::Pos, new_key: Self::Key) {
+        if old_key != new_key {
+            self.delete(old_key, &pos);
+            self.insert(new_key, pos);
+        }
+    }
+
+    fn delete(&mut self, (key, value): Self::Key, pos: &Self::Pos) {
+        if let Some((p, aggregate)) = self.0.get_mut(&key) {
+            let len = p.as_position_slice().len();
+            let is_empty = p.remove_pos(pos);
+            // the aggregate is only changed, if the `pos` was removed
+            if p.as_position_slice().len() != len {
+                aggregate.remove(value);
+            }
+            if is_empty {
+                self.0.remove(&key);
+            }
+        }
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self(HashMap::with_capacity(capacity))
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional)
+    }
+
+    fn clear(&mut self) {
+        self.0.clear()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, V, P> MergeStore for AggregateStore<K, V, P>
+where
+    K: Hash + Eq,
+    V: PartialOrd + Clone + Default + AddAssign + SubAssign,
+    P: MergeKeyPosition + KeyPositionAsSlice,
+{
+    fn merge(&mut self, other: Self) {
+        other
+            .0
+            .into_iter()
+            .for_each(|(key, (pos, aggregate))| match self.0.get_mut(&key) {
+                Some((p, a)) => {
+                    p.merge(pos);
+                    a.count += aggregate.count;
+                    a.sum += aggregate.sum;
+                    aggregate
+                        .values
+                        .into_iter()
+                        .for_each(|(v, n)| *a.values.entry(v).or_default() += n);
+                }
+                None => {
+                    self.0.insert(key, (pos, aggregate));
+                }
+            });
+    }
+}
+
+/// A proxy for exposing [`AggregateStore`] specific extensions.
+#[repr(transparent)]
+pub struct AggregateStoreExt<K, V, P>(AggregateStore<K, V, P>);
+
+impl<K, V, P> Deref for AggregateStore<K, V, P> {
+    type Target = AggregateStoreExt<K, V, P>;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFTY:
+        // self is a valid pointer and
+        // AggregateStoreExt is repr(transparent) thus has the same memory layout like AggregateStore
+        unsafe { &*(self as *const AggregateStore<K, V, P> as *const AggregateStoreExt<K, V, P>) }
+    }
+}
+
+impl<K, V, P> AggregateStoreExt<K, V, P>
+where
+    K: Hash + Eq,
+{
+    pub fn keys(&self) -> impl Iterator<Item = &'_ K> {
+        self.0 .0.keys()
+    }
+
+    /// Returns the [`Aggregate`] for the given `Key`.
+    pub fn aggregate<Q>(&self, key: &Q) -> Option<&Aggregate<V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.0 .0.get(key).map(|(_, a)| a)
+    }
+
+    /// Returns all `Key`s with the associated [`Aggregate`].
+    pub fn aggregates(&self) -> impl Iterator<Item = (&'_ K, &'_ Aggregate<V>)> {
+        self.0 .0.iter().map(|(k, (_, a))| (k, a))
+    }
+
+    /// Returns the count of values for the given `Key`.
+    pub fn count<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.aggregate(key).map(Aggregate::count)
+    }
+
+    /// Returns the sum of values for the given `Key`.
+    pub fn sum<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.aggregate(key).map(Aggregate::sum)
+    }
+
+    /// Returns the smallest value for the given `Key`.
+    pub fn min<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.aggregate(key).and_then(Aggregate::min)
+    }
+
+    /// Returns the greatest value for the given `Key`.
+    pub fn max<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.aggregate(key).and_then(Aggregate::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lookup::store::position::MultiKeyPosition;
+
+    type MultiKeyAggregate = AggregateStore<&'static str, i32, MultiKeyPosition<usize>>;
+
+    #[test]
+    fn insert_update_delete() {
+        let mut idx = MultiKeyAggregate::with_capacity(0);
+        idx.insert(("a", 5), 0);
+        idx.insert(("a", 3), 1);
+        idx.insert(("b", 7), 2);
+        idx.insert(("a", 5), 3);
+
+        assert_eq!(&[0, 1, 3], idx.pos_by_key("a"));
+        assert_eq!(Some(3), idx.count("a"));
+        assert_eq!(Some(&13), idx.sum("a"));
+        assert_eq!(Some(&3), idx.min("a"));
+        assert_eq!(Some(&5), idx.max("a"));
+
+        // change the value
+        idx.update(("a", 3), 1, ("a", 9));
+        assert_eq!(Some(&19), idx.sum("a"));
+        assert_eq!(Some(&5), idx.min("a"));
+        assert_eq!(Some(&9), idx.max("a"));
+
+        // change the key
+        idx.update(("a", 9), 1, ("b", 9));
+        assert_eq!(Some(2), idx.count("a"));
+        assert_eq!(Some(&16), idx.sum("b"));
+
+        // one 5 is still there
+        idx.delete(("a", 5), &0);
+        assert_eq!(Some(&5), idx.min("a"));
+        assert_eq!(Some(&5), idx.max("a"));
+
+        idx.delete(("a", 5), &3);
+        assert!(!idx.key_exist("a"));
+        assert!(idx.aggregate("a").is_none());
+    }
+
+    #[test]
+    fn delete_unknown_pos() {
+        let mut idx = MultiKeyAggregate::with_capacity(0);
+        idx.insert(("a", 5), 0);
+        idx.insert(("a", 5), 1);
+
+        // the pos 7 does not exist for the key, the aggregate is not changed
+        idx.delete(("a", 5), &7);
+        assert_eq!(Some(2), idx.count("a"));
+        assert_eq!(Some(&10), idx.sum("a"));
+
+        idx.delete(("a", 5), &1);
+        assert_eq!(Some(1), idx.count("a"));
+        assert_eq!(Some(&5), idx.sum("a"));
+    }
+
+    #[test]
+    fn float_values() {
+        let mut idx = AggregateStore::<&str, f64, MultiKeyPosition<usize>>::with_capacity(0);
+        idx.insert(("a", 2.5), 0);
+        idx.insert(("a", -1.0), 1);
+        assert_eq!(Some(&1.5), idx.sum("a"));
+        idx.insert(("a", f64::NAN), 2);
+
+        assert_eq!(Some(3), idx.count("a"));
+        assert_eq!(Some(&-1.0), idx.min("a"));
+        // NaN is the greatest value
+        assert!(idx.max("a").unwrap().is_nan());
+
+        idx.delete(("a", f64::NAN), &2);
+        assert_eq!(Some(&2.5), idx.max("a"));
+        assert_eq!(Some(2), idx.count("a"));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn merge() {
+        let mut idx = MultiKeyAggregate::with_capacity(0);
+        idx.insert(("a", 5), 0);
+        idx.insert(("b", 1), 1);
+
+        let mut other = MultiKeyAggregate::with_capacity(0);
+        other.insert(("a", 2), 2);
+        other.insert(("c", 4), 3);
+
+        idx.merge(other);
+        assert_eq!(&[0, 2], idx.pos_by_key("a"));
+        assert_eq!(Some(&7), idx.sum("a"));
+        assert_eq!(Some(&2), idx.min("a"));
+        assert_eq!(Some(1), idx.count("c"));
+    }
+}
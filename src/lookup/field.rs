@@ -0,0 +1,181 @@
+//! A `Field` is the connection between an `Item` and the `Store`.
+//! It extracts the `Key` from the `Item` and keeps the `Store` in sync, by every write operation.
+//!
+//! There are two kinds of `Field`s:
+//!
+//! - every function `Fn(&Item) -> Key`: the `Key` is created (maybe cloned) for every write operation
+//! - [`Borrowed`]: the function returns a borrowed `Key` (e.g. `&str`),
+//!   the `Store` creates an owned `Key` only, if the `Key` does not exist in the `Store`
+//!   (see [`crate::lookup::store::BorrowedStore`])
+//!
+use crate::lookup::store::{BorrowedStore, Store};
+use std::{
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+};
+
+/// `Field` extracts the `Key` from the `Item` and write them with the `Position` in the `Store`.
+pub trait Field<S, I>
+where
+    S: Store,
+{
+    /// Insert the `Key` from the `item` with the `pos` into the `Store`.
+    fn insert(&self, store: &mut S, item: &I, pos: S::Pos);
+
+    /// Insert all `Key`s from the `items` with the associated `Position`s into the `Store`.
+    fn insert_many<'a, It>(&self, store: &mut S, items: It)
+    where
+        It: IntoIterator<Item = (&'a I, S::Pos)>,
+        I: 'a;
+
+    /// Call the `update` function for the `item` and update the `Store`, if the `Key` is changed.
+    fn update<U>(&self, store: &mut S, item: &mut I, pos: S::Pos, update: U)
+    where
+        U: FnOnce(&mut I);
+
+    /// The `item` moved from the `old_pos` to the `new_pos` (the `Key` is not changed).
+    fn update_pos(&self, store: &mut S, item: &I, old_pos: &S::Pos, new_pos: S::Pos);
+
+    /// Delete the `Key` from the `item` with the `pos` from the `Store`.
+    fn delete(&self, store: &mut S, item: &I, pos: &S::Pos);
+}
+
+impl<S, I, F> Field<S, I> for F
+where
+    S: Store,
+    F: Fn(&I) -> S::Key,
+{
+    fn insert(&self, store: &mut S, item: &I, pos: S::Pos) {
+        store.insert(self(item), pos)
+    }
+
+    fn insert_many<'a, It>(&self, store: &mut S, items: It)
+    where
+        It: IntoIterator<Item = (&'a I, S::Pos)>,
+        I: 'a,
+    {
+        store.insert_many(items.into_iter().map(|(item, pos)| (self(item), pos)))
+    }
+
+    fn update<U>(&self, store: &mut S, item: &mut I, pos: S::Pos, update: U)
+    where
+        U: FnOnce(&mut I),
+    {
+        let old_key = self(item);
+        update(item);
+        store.update(old_key, pos, self(item))
+    }
+
+    fn update_pos(&self, store: &mut S, item: &I, old_pos: &S::Pos, new_pos: S::Pos) {
+        store.delete(self(item), old_pos);
+        store.insert(self(item), new_pos)
+    }
+
+    fn delete(&self, store: &mut S, item: &I, pos: &S::Pos) {
+        store.delete(self(item), pos)
+    }
+}
+
+/// [`Borrowed`] is a `Field`, where the function returns a borrowed `Key`.
+///
+/// # Example
+///
+/// ```
+/// use lookups::{Borrowed, LkupVec, HashLookup, Lookup};
+///
+/// #[derive(PartialEq, Debug)]
+/// struct Person {
+///     id: usize,
+///     name: String,
+/// }
+///
+/// let mut vec = LkupVec::new(
+///     HashLookup::with_multi_keys(),
+///     // no String is cloned, the Store creates an owned String only for a new Key
+///     Borrowed::new(|p: &Person| p.name.as_str()),
+/// );
+///
+/// vec.push(Person{id: 0, name: "Paul".into()});
+/// vec.push(Person{id: 5, name: "Mario".into()});
+///
+/// // update without changing the Key (no allocation)
+/// vec.update(1, |p| p.id = 7);
+///
+/// assert_eq!(
+///     &Person{id: 7, name:  "Mario".into()},
+///     vec.get_by_lkup_key("Mario").next().unwrap()
+/// );
+/// ```
+pub struct Borrowed<F, Q: ?Sized>(F, PhantomData<fn() -> *const Q>);
+
+impl<F, Q: ?Sized> Borrowed<F, Q> {
+    /// Create a new `Field` with a function, which returns a borrowed `Key`.
+    pub fn new<I>(field: F) -> Self
+    where
+        F: Fn(&I) -> &Q,
+    {
+        Self(field, PhantomData)
+    }
+}
+
+impl<F, Q> Clone for Borrowed<F, Q>
+where
+    F: Clone,
+    Q: ?Sized,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<F, Q: ?Sized> std::fmt::Debug for Borrowed<F, Q> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Borrowed")
+    }
+}
+
+impl<S, I, F, Q> Field<S, I> for Borrowed<F, Q>
+where
+    S: BorrowedStore<Q>,
+    F: Fn(&I) -> &Q,
+    Q: ?Sized,
+{
+    fn insert(&self, store: &mut S, item: &I, pos: S::Pos) {
+        store.attach((self.0)(item), pos, None)
+    }
+
+    fn insert_many<'a, It>(&self, store: &mut S, items: It)
+    where
+        It: IntoIterator<Item = (&'a I, S::Pos)>,
+        I: 'a,
+    {
+        items
+            .into_iter()
+            .for_each(|(item, pos)| store.attach((self.0)(item), pos, None))
+    }
+
+    fn update<U>(&self, store: &mut S, item: &mut I, pos: S::Pos, update: U)
+    where
+        U: FnOnce(&mut I),
+    {
+        let taken = store.take_entry((self.0)(item), &pos);
+        // the taken entry must be restored, also if the `update` panics,
+        // otherwise all `Position`s of the taken entry are lost
+        let updated = panic::catch_unwind(AssertUnwindSafe(|| update(item)));
+        store.restore_entry((self.0)(item), pos, taken);
+
+        if let Err(err) = updated {
+            panic::resume_unwind(err)
+        }
+    }
+
+    fn update_pos(&self, store: &mut S, item: &I, old_pos: &S::Pos, new_pos: S::Pos) {
+        let key = (self.0)(item);
+        let detached = store.detach(key, old_pos);
+        store.attach(key, new_pos, detached)
+    }
+
+    fn delete(&self, store: &mut S, item: &I, pos: &S::Pos) {
+        store.detach((self.0)(item), pos);
+    }
+}
@@ -0,0 +1,443 @@
+//! The `frozen` module contains a hashing lookup for __read only__ collections (e.g. [`crate::collections::list::ro::LkupList`]).
+//! All `Key`s are saved in one array, sorted by the hash value. A `Key` is found by a binary search over the hash values.
+//!
+//! ### Advantages:
+//! - smaller memory footprint (no growth headroom, no empty buckets)
+//! - fast lookup for a `Store`, which is not changed after the construction
+//!
+//! ### Disadvantages:
+//! - inserting or removing a single `Key` is expensive, the array must be moved
+//!
+#[cfg(feature = "rayon")]
+use crate::lookup::store::{position::MergeKeyPosition, MergeStore};
+use crate::lookup::{
+    hash::{DefaultHashBuilder, HashStore},
+    store::{
+        position::{KeyPosition, KeyPositionAsSlice},
+        Groups, Lookup, Positions, Retriever, Store, View, ViewCreator,
+    },
+};
+use std::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+    ops::Deref,
+};
+
+/// `FrozenHashLookup` is the creator for the `FrozenHashStore`: `Retriever` and `Store`.
+///
+/// # Example
+///
+/// ```
+/// use lookups::{collections::list::ro::LkupList, lookup::frozen::FrozenHashLookup, Lookup};
+///
+/// let list = LkupList::new(
+///     FrozenHashLookup::with_multi_keys(),
+///     |s: &String| s.clone(),
+///     vec![String::from("Paul"), String::from("Mario"), String::from("Paul")],
+/// );
+///
+/// assert_eq!(2, list.get_by_lkup_key("Paul").count());
+/// assert!(!list.contains_lkup_key("Jasmin"));
+/// ```
+pub struct FrozenHashLookup<K, P>(PhantomData<K>, PhantomData<P>);
+
+impl<K, P> Lookup<FrozenHashStore<K, P>, P> for FrozenHashLookup<K, P>
+where
+    K: Hash + Eq,
+    P: KeyPosition,
+{
+    fn new() -> Self {
+        Self(PhantomData, PhantomData)
+    }
+}
+
+/// `FrozenHashStore` is an implementation for a hash index, where all `Key`s are saved in one array,
+/// sorted by the hash value.
+///
+#[derive(Debug, Clone)]
+pub struct FrozenHashStore<K, P, H = DefaultHashBuilder> {
+    hasher: H,
+    entries: Vec<(u64, K, P)>,
+}
+
+impl<K, P, H> FrozenHashStore<K, P, H>
+where
+    H: BuildHasher,
+{
+    /// Returns `Ok` with the index of the `key` or `Err` with the index, where the `key` can be inserted.
+    fn find<Q>(&self, hash: u64, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let start = self.entries.partition_point(|(h, _, _)| *h < hash);
+
+        self.entries[start..]
+            .iter()
+            .take_while(|(h, _, _)| *h == hash)
+            .position(|(_, k, _)| k.borrow() == key)
+            .map(|idx| start + idx)
+            .ok_or(start)
+    }
+
+    fn get<Q>(&self, key: &Q) -> Option<&(u64, K, P)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.find(self.hasher.hash_one(key), key).ok()?;
+        Some(&self.entries[idx])
+    }
+}
+
+impl<K, P, H> FrozenHashStore<K, P, H>
+where
+    K: Hash + Eq,
+    P: KeyPosition,
+    H: BuildHasher,
+{
+    // the `Key`s must be unique and not in the `Store` and the `KeyPosition`s must be normalized
+    fn append_new(&mut self, mut new_entries: Vec<(u64, K, P)>) {
+        if !new_entries.is_empty() {
+            self.entries.append(&mut new_entries);
+            self.entries.sort_by_key(|(h, _, _)| *h);
+        }
+        self.entries.shrink_to_fit();
+    }
+}
+
+impl<Q, K, P, H> Retriever<&Q> for FrozenHashStore<K, P, H>
+where
+    K: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+    P: KeyPositionAsSlice,
+    H: BuildHasher,
+{
+    type Pos = P::Pos;
+
+    fn key_exist(&self, key: &Q) -> bool {
+        self.get(key).is_some()
+    }
+
+    fn pos_by_key(&self, key: &Q) -> &[Self::Pos] {
+        match self.get(key) {
+            Some((_, _, p)) => p.as_position_slice(),
+            None => &[],
+        }
+    }
+}
+
+impl<'a, K, P, H> ViewCreator<'a> for FrozenHashStore<K, P, H>
+where
+    K: Hash + Eq + Clone,
+    P: KeyPositionAsSlice + 'a,
+    H: BuildHasher + Clone,
+{
+    type Key = K;
+    type Retriever = FrozenHashStore<K, &'a P, H>;
+
+    fn create_view<It>(&'a self, keys: It) -> View<Self::Retriever>
+    where
+        It: IntoIterator<Item = Self::Key>,
+    {
+        // the indices are in the same order like the hash values
+        let mut indices = keys
+            .into_iter()
+            .filter_map(|key| self.find(self.hasher.hash_one(&key), &key).ok())
+            .collect::<Vec<_>>();
+        indices.sort_unstable();
+        indices.dedup();
+
+        View::new(FrozenHashStore {
+            hasher: self.hasher.clone(),
+            entries: indices
+                .into_iter()
+                .map(|idx| {
+                    let (h, k, p) = &self.entries[idx];
+                    (*h, k.clone(), p)
+                })
+                .collect(),
+        })
+    }
+}
+
+impl<K, P, H> Positions for FrozenHashStore<K, P, H>
+where
+    P: KeyPositionAsSlice,
+{
+    type Pos = P::Pos;
+
+    fn positions(&self) -> impl Iterator<Item = &'_ P::Pos> {
+        self.entries
+            .iter()
+            .flat_map(|(_, _, p)| p.as_position_slice())
+    }
+}
+
+impl<K, P, H> Groups for FrozenHashStore<K, P, H>
+where
+    P: KeyPositionAsSlice,
+{
+    type Key = K;
+    type Pos = P::Pos;
+
+    fn groups(&self) -> impl Iterator<Item = (&'_ K, &'_ [P::Pos])> {
+        self.entries
+            .iter()
+            .map(|(_, k, p)| (k, p.as_position_slice()))
+    }
+}
+
+impl<K, P, H> Store for FrozenHashStore<K, P, H>
+where
+    K: Hash + Eq,
+    P: KeyPosition,
+    H: BuildHasher + Default + Clone,
+{
+    type Key = K;
+    type Pos = P::Pos;
+
+    fn insert(&mut self, key: Self::Key, pos: Self::Pos) {
+        let hash = self.hasher.hash_one(&key);
+        match self.find(hash, &key) {
+            Ok(idx) => self.entries[idx].2.add_pos(pos),
+            Err(idx) => self.entries.insert(idx, (hash, key, P::from_pos(pos))),
+        }
+    }
+
+    fn insert_many<It>(&mut self, it: It)
+    where
+        It: IntoIterator<Item = (Self::Key, Self::Pos)>,
+    {
+        // the Positions of existing Keys are added directly
+        let mut new_pairs = Vec::new();
+        for (key, pos) in it {
+            let hash = self.hasher.hash_one(&key);
+            match self.find(hash, &key) {
+                Ok(idx) => self.entries[idx].2.add_pos(pos),
+                Err(_) => new_pairs.push((key, pos)),
+            }
+        }
+
+        // group the Positions of the new Keys, before the array is sorted
+        let mut store = HashStore::<K, P, H>::with_capacity(0);
+        store.insert_many(new_pairs);
+        let new_entries = store
+            .0
+            .into_iter()
+            .map(|(k, p)| (self.hasher.hash_one(&k), k, p))
+            .collect();
+        self.append_new(new_entries);
+    }
+
+    /// If the `Key` is not changed, the `Store` is not touched.
+    fn update(&mut self, old_key: Self::Key, pos: Self::Pos, new_key: Self::Key) {
+        if old_key != new_key {
+            self.delete(old_key, &pos);
+            self.insert(new_key, pos);
+        }
+    }
+
+    fn delete(&mut self, key: Self::Key, pos: &Self::Pos) {
+        if let Ok(idx) = self.find(self.hasher.hash_one(&key), &key) {
+            if self.entries[idx].2.remove_pos(pos) {
+                self.entries.remove(idx);
+            }
+        }
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            hasher: H::default(),
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn new_like(&self, capacity: usize) -> Self {
+        Self {
+            hasher: self.hasher.clone(),
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.entries.reserve(additional)
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, P, H> MergeStore for FrozenHashStore<K, P, H>
+where
+    K: Hash + Eq,
+    P: MergeKeyPosition,
+    H: BuildHasher + Default + Clone,
+{
+    fn merge(&mut self, other: Self) {
+        let mut new_entries = Vec::new();
+
+        for (_, key, p) in other.entries {
+            // the hash values are recalculated, because the hasher can be different
+            let hash = self.hasher.hash_one(&key);
+            match self.find(hash, &key) {
+                Ok(idx) => self.entries[idx].2.merge(p),
+                Err(_) => new_entries.push((hash, key, p)),
+            }
+        }
+
+        self.append_new(new_entries);
+    }
+}
+
+impl<K, P, H> HashStore<K, P, H>
+where
+    K: Hash + Eq,
+    P: KeyPosition,
+    H: BuildHasher + Clone,
+{
+    /// Convert the `HashStore` into a [`FrozenHashStore`] with the same `BuildHasher`.
+    pub fn freeze(self) -> FrozenHashStore<K, P, H> {
+        let hasher = self.0.hasher().clone();
+        let mut entries = self
+            .0
+            .into_iter()
+            .map(|(k, p)| (hasher.hash_one(&k), k, p))
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|(h, _, _)| *h);
+
+        FrozenHashStore { hasher, entries }
+    }
+}
+
+/// A proxy for exposing [`FrozenHashStore`] specific extensions.
+#[repr(transparent)]
+pub struct FrozenHashStoreExt<K, P, H>(FrozenHashStore<K, P, H>);
+
+impl<K, P, H> Deref for FrozenHashStore<K, P, H> {
+    type Target = FrozenHashStoreExt<K, P, H>;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFTY:
+        // self is a valid pointer and
+        // FrozenHashStoreExt is repr(transparent) thus has the same memory layout like FrozenHashStore
+        unsafe { &*(self as *const FrozenHashStore<K, P, H> as *const FrozenHashStoreExt<K, P, H>) }
+    }
+}
+
+impl<K, P, H> FrozenHashStoreExt<K, P, H> {
+    pub fn keys(&self) -> impl Iterator<Item = &'_ K> {
+        self.0.entries.iter().map(|(_, k, _)| k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lookup::store::position::{MultiKeyPosition, UniqueKeyPosition};
+
+    type MultiKeyFrozen<K = String, X = usize> = FrozenHashStore<K, MultiKeyPosition<X>>;
+
+    #[test]
+    fn store_and_lookup() {
+        let mut idx = MultiKeyFrozen::with_capacity(0);
+        idx.insert_many([
+            (String::from("a"), 3),
+            (String::from("b"), 1),
+            (String::from("a"), 0),
+        ]);
+        idx.insert(String::from("c"), 2);
+        idx.insert(String::from("b"), 4);
+
+        assert!(idx.key_exist("a"));
+        assert!(!idx.key_exist("z"));
+        assert_eq!(&[0, 3], idx.pos_by_key("a"));
+        assert_eq!(&[1, 4], idx.pos_by_key("b"));
+        assert_eq!(&[2], idx.pos_by_key("c"));
+        assert_eq!(&[0usize; 0], idx.pos_by_key("z"));
+
+        idx.update(String::from("c"), 2, String::from("a"));
+        assert!(!idx.key_exist("c"));
+        assert_eq!(&[0, 2, 3], idx.pos_by_key("a"));
+
+        idx.delete(String::from("b"), &1);
+        idx.delete(String::from("b"), &4);
+        assert!(!idx.key_exist("b"));
+
+        let mut keys = idx.keys().collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(vec!["a"], keys);
+    }
+
+    #[test]
+    fn insert_many_with_existing_keys() {
+        let mut idx = MultiKeyFrozen::with_capacity(0);
+        idx.insert_many([(String::from("a"), 3), (String::from("b"), 1)]);
+        idx.insert_many([
+            (String::from("c"), 7),
+            (String::from("a"), 0),
+            (String::from("c"), 2),
+            (String::from("a"), 5),
+        ]);
+
+        assert_eq!(&[0, 3, 5], idx.pos_by_key("a"));
+        assert_eq!(&[1], idx.pos_by_key("b"));
+        assert_eq!(&[2, 7], idx.pos_by_key("c"));
+    }
+
+    #[test]
+    fn freeze_hash_store() {
+        let mut hash = HashStore::<String, UniqueKeyPosition<usize>>::with_capacity(3);
+        hash.insert(String::from("a"), 0);
+        hash.insert(String::from("b"), 1);
+        hash.insert(String::from("c"), 2);
+
+        let idx = hash.freeze();
+        assert_eq!(3, idx.keys().count());
+        assert_eq!(&[1], idx.pos_by_key("b"));
+        assert!(!idx.key_exist("z"));
+    }
+
+    #[test]
+    fn create_view() {
+        let mut idx = MultiKeyFrozen::with_capacity(0);
+        idx.insert_many([
+            (String::from("a"), 0),
+            (String::from("b"), 1),
+            (String::from("c"), 2),
+            (String::from("b"), 3),
+        ]);
+
+        let view = idx.create_view([
+            String::from("b"),
+            String::from("c"),
+            String::from("b"),
+            String::from("z"),
+        ]);
+        assert!(!view.key_exist("a"));
+        assert_eq!(&[1, 3], view.pos_by_key("b"));
+        assert_eq!(&[2], view.pos_by_key("c"));
+
+        let mut positions = view.positions().collect::<Vec<_>>();
+        positions.sort();
+        assert_eq!(vec![&1, &2, &3], positions);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn merge() {
+        let mut idx = MultiKeyFrozen::with_capacity(0);
+        idx.insert_many([(String::from("a"), 3), (String::from("b"), 1)]);
+
+        let mut other = MultiKeyFrozen::with_capacity(0);
+        other.insert_many([(String::from("c"), 2), (String::from("a"), 0)]);
+
+        idx.merge(other);
+        assert_eq!(&[0, 3], idx.pos_by_key("a"));
+        assert_eq!(&[1], idx.pos_by_key("b"));
+        assert_eq!(&[2], idx.pos_by_key("c"));
+    }
+}
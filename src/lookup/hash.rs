@@ -3,28 +3,84 @@
 //! ### Advantages:
 //! - all advantages, which has a hashing procedure
 //!
+#[cfg(feature = "rayon")]
+use crate::lookup::store::{position::MergeKeyPosition, MergeStore};
 use crate::lookup::store::{
     position::{KeyPosition, KeyPositionAsSlice},
-    Lookup, Positions, Retriever, Store, View, ViewCreator,
+    BorrowedStore, Groups, Lookup, Positions, Retriever, Store, View, ViewCreator,
 };
-use std::{borrow::Borrow, hash::Hash, marker::PhantomData, ops::Deref};
+use std::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+    ops::Deref,
+};
+
+/// The default `BuildHasher`, which is used by the `HashMap`.
+#[cfg(feature = "hashbrown")]
+pub type DefaultHashBuilder = hashbrown::hash_map::DefaultHashBuilder;
+
+/// The default `BuildHasher`, which is used by the `HashMap`.
+#[cfg(not(feature = "hashbrown"))]
+pub type DefaultHashBuilder = std::collections::hash_map::RandomState;
+
+#[cfg(feature = "hashbrown")]
+type HashMap<K, V, H> = hashbrown::HashMap<K, V, H>;
+
+#[cfg(not(feature = "hashbrown"))]
+type HashMap<K, V, H> = std::collections::HashMap<K, V, H>;
 
 #[cfg(feature = "hashbrown")]
-type HashMap<K, V> = hashbrown::HashMap<K, V>;
+use hashbrown::hash_map::Entry;
 
 #[cfg(not(feature = "hashbrown"))]
-type HashMap<K, V> = std::collections::HashMap<K, V>;
+use std::collections::hash_map::Entry;
 
-/// `IndexLookup` is the creator for the `HashStore`: `Retriever` and `Store`.
-pub struct HashLookup<K, P>(PhantomData<K>, PhantomData<P>);
+/// `HashLookup` is the creator for the `HashStore`: `Retriever` and `Store`,
+/// with the [`DefaultHashBuilder`].
+///
+/// For using an other `BuildHasher`, see: [`HashLookupWith::with_hasher`].
+/// A `View` of the `HashStore` clones the `Key`s, for sharing the `Key`s with the `View`s,
+/// see the opt-in alternative: [`crate::InternLookup`].
+pub type HashLookup<K, P> = HashLookupWith<K, P, DefaultHashBuilder>;
+
+/// `HashLookupWith` is the creator for the `HashStore`: `Retriever` and `Store`,
+/// where all created `Store`s are using the given `BuildHasher`.
+pub struct HashLookupWith<K, P, H>(PhantomData<K>, PhantomData<P>, H);
+
+impl<K, P, H> HashLookupWith<K, P, H> {
+    /// Replace the `BuildHasher`, which is used for all created `Store`s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lookups::{HashLookup, LkupVec, Lookup};
+    /// use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+    ///
+    /// // a fixed hasher, e.g. for reproducible tests
+    /// let lookup = HashLookup::with_unique_key()
+    ///     .with_hasher(BuildHasherDefault::<DefaultHasher>::default());
+    ///
+    /// let vec = LkupVec::from_iter(lookup, |s: &String| s.clone(), [String::from("Jasmin")]);
+    /// assert!(vec.contains_lkup_key("Jasmin"));
+    /// ```
+    pub fn with_hasher<B>(self, hash_builder: B) -> HashLookupWith<K, P, B> {
+        HashLookupWith(PhantomData, PhantomData, hash_builder)
+    }
+}
 
-impl<K, P> Lookup<HashStore<K, P>, P> for HashLookup<K, P>
+impl<K, P, H> Lookup<HashStore<K, P, H>, P> for HashLookupWith<K, P, H>
 where
     P: KeyPosition,
     K: Hash + Eq,
+    H: BuildHasher + Default + Clone,
 {
     fn new() -> Self {
-        Self(PhantomData, PhantomData)
+        Self(PhantomData, PhantomData, H::default())
+    }
+
+    fn new_store(&self, capacity: usize) -> HashStore<K, P, H> {
+        HashStore(HashMap::with_capacity_and_hasher(capacity, self.2.clone()))
     }
 }
 
@@ -32,13 +88,14 @@ where
 ///
 #[derive(Debug, Clone)]
 #[repr(transparent)]
-pub struct HashStore<K, P>(HashMap<K, P>);
+pub struct HashStore<K, P, H = DefaultHashBuilder>(pub(crate) HashMap<K, P, H>);
 
-impl<Q, K, P> Retriever<&Q> for HashStore<K, P>
+impl<Q, K, P, H> Retriever<&Q> for HashStore<K, P, H>
 where
     K: Borrow<Q> + Hash + Eq,
     Q: Hash + Eq + ?Sized,
     P: KeyPositionAsSlice,
+    H: BuildHasher,
 {
     type Pos = P::Pos;
 
@@ -54,19 +111,20 @@ where
     }
 }
 
-impl<'a, K, P> ViewCreator<'a> for HashStore<K, P>
+impl<'a, K, P, H> ViewCreator<'a> for HashStore<K, P, H>
 where
     K: Hash + Eq + Clone,
     P: KeyPositionAsSlice + 'a,
+    H: BuildHasher + Clone,
 {
     type Key = K;
-    type Retriever = HashStore<K, &'a P>;
+    type Retriever = HashStore<K, &'a P, H>;
 
     fn create_view<It>(&'a self, keys: It) -> View<Self::Retriever>
     where
         It: IntoIterator<Item = Self::Key>,
     {
-        let mut map = HashMap::<K, &P>::with_capacity(self.0.len());
+        let mut map = HashMap::with_capacity_and_hasher(self.0.len(), self.0.hasher().clone());
 
         for key in keys {
             if let Some(p) = self.0.get(&key) {
@@ -78,7 +136,7 @@ where
     }
 }
 
-impl<'a, K, P> Positions for HashStore<K, &'a P>
+impl<K, P, H> Positions for HashStore<K, P, H>
 where
     P: KeyPositionAsSlice,
 {
@@ -89,55 +147,228 @@ where
     }
 }
 
-impl<K, P> Store for HashStore<K, P>
+impl<K, P, H> Groups for HashStore<K, P, H>
+where
+    P: KeyPositionAsSlice,
+{
+    type Key = K;
+    type Pos = P::Pos;
+
+    fn groups(&self) -> impl Iterator<Item = (&'_ K, &'_ [P::Pos])> {
+        self.0.iter().map(|(k, p)| (k, p.as_position_slice()))
+    }
+}
+
+impl<K, P, H> Store for HashStore<K, P, H>
 where
     K: Hash + Eq,
     P: KeyPosition,
+    H: BuildHasher + Default + Clone,
 {
     type Key = K;
     type Pos = P::Pos;
 
     fn insert(&mut self, key: Self::Key, pos: Self::Pos) {
-        match self.0.get_mut(&key) {
-            Some(p) => p.add_pos(pos),
-            None => {
-                self.0.insert(key, P::from_pos(pos));
+        match self.0.entry(key) {
+            Entry::Occupied(mut o) => o.get_mut().add_pos(pos),
+            Entry::Vacant(v) => {
+                v.insert(P::from_pos(pos));
             }
         }
     }
 
+    /// If the `Key` is not changed, the `Store` is not touched.
+    fn update(&mut self, old_key: Self::Key, pos: Self::Pos, new_key: Self::Key) {
+        if old_key != new_key {
+            self.delete(old_key, &pos);
+            self.insert(new_key, pos);
+        }
+    }
+
+    fn insert_many<It>(&mut self, it: It)
+    where
+        It: IntoIterator<Item = (Self::Key, Self::Pos)>,
+    {
+        let it = it.into_iter();
+        self.0.reserve(it.size_hint().0);
+
+        // an empty Store: all Keys are touched by this batch
+        if self.0.is_empty() {
+            Self::push_all(&mut self.0, it);
+            self.0.values_mut().for_each(P::normalize);
+            return;
+        }
+
+        // the Positions of existing Keys are added directly,
+        // the new Keys are grouped in a batch first, so only the new Keys are normalized
+        let mut batch = HashMap::with_hasher(H::default());
+        let map = &mut self.0;
+        Self::push_all(
+            &mut batch,
+            it.filter_map(|(key, pos)| match map.get_mut(&key) {
+                Some(p) => {
+                    p.add_pos(pos);
+                    None
+                }
+                None => Some((key, pos)),
+            }),
+        );
+
+        batch.values_mut().for_each(P::normalize);
+        self.0.extend(batch);
+    }
+
     fn delete(&mut self, key: Self::Key, pos: &Self::Pos) {
-        if let Some(rm_idx) = self.0.get_mut(&key) {
-            if rm_idx.remove_pos(pos) {
-                self.0.remove(&key);
+        if let Entry::Occupied(mut o) = self.0.entry(key) {
+            if o.get_mut().remove_pos(pos) {
+                o.remove();
             }
         }
     }
 
     fn with_capacity(capacity: usize) -> Self {
-        HashStore(HashMap::with_capacity(capacity))
+        HashStore(HashMap::with_capacity_and_hasher(capacity, H::default()))
+    }
+
+    fn new_like(&self, capacity: usize) -> Self {
+        HashStore(HashMap::with_capacity_and_hasher(
+            capacity,
+            self.0.hasher().clone(),
+        ))
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional)
+    }
+
+    fn clear(&mut self) {
+        self.0.clear()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, P, H> MergeStore for HashStore<K, P, H>
+where
+    K: Hash + Eq,
+    P: MergeKeyPosition,
+    H: BuildHasher + Default + Clone,
+{
+    fn merge(&mut self, other: Self) {
+        other
+            .0
+            .into_iter()
+            .for_each(|(key, pos)| match self.0.get_mut(&key) {
+                Some(p) => p.merge(pos),
+                None => {
+                    self.0.insert(key, pos);
+                }
+            });
+    }
+}
+
+impl<K, P, H> HashStore<K, P, H>
+where
+    K: Hash + Eq,
+    P: KeyPosition,
+    H: BuildHasher,
+{
+    // add all Positions without normalizing
+    fn push_all<It>(map: &mut HashMap<K, P, H>, it: It)
+    where
+        It: Iterator<Item = (K, P::Pos)>,
+    {
+        it.for_each(|(key, pos)| match map.entry(key) {
+            Entry::Occupied(mut o) => o.get_mut().push_pos(pos),
+            Entry::Vacant(v) => {
+                v.insert(P::from_pos(pos));
+            }
+        });
+    }
+}
+
+impl<Q, K, P, H> BorrowedStore<Q> for HashStore<K, P, H>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: ToOwned<Owned = K> + Hash + Eq + ?Sized,
+    P: KeyPosition,
+    H: BuildHasher + Default + Clone,
+{
+    type Detached = (K, P);
+
+    fn detach(&mut self, key: &Q, pos: &Self::Pos) -> Option<Self::Detached> {
+        if self.0.get_mut(key)?.remove_pos(pos) {
+            return self.0.remove_entry(key);
+        }
+        None
+    }
+
+    fn attach(&mut self, key: &Q, pos: Self::Pos, detached: Option<Self::Detached>) {
+        if let Some(p) = self.0.get_mut(key) {
+            p.add_pos(pos);
+            return;
+        }
+
+        // reuse the owned Key, if it is the same Key and the (empty) KeyPosition
+        let (key, mut p) = match detached {
+            Some((k, p)) if k.borrow() == key => (k, p),
+            Some((_, p)) => (key.to_owned(), p),
+            None => {
+                self.0.insert(key.to_owned(), P::from_pos(pos));
+                return;
+            }
+        };
+        p.add_pos(pos);
+        self.0.insert(key, p);
+    }
+
+    /// The entry is removed with all `Position`s, the `pos` stays in the entry.
+    fn take_entry(&mut self, key: &Q, _pos: &Self::Pos) -> Option<Self::Detached> {
+        self.0.remove_entry(key)
+    }
+
+    /// If the `Key` is not changed, the entry is reinserted, without touching the `Position`s.
+    fn restore_entry(&mut self, key: &Q, pos: Self::Pos, taken: Option<Self::Detached>) {
+        match taken {
+            Some((k, p)) if k.borrow() == key => {
+                self.0.insert(k, p);
+            }
+            Some((k, mut p)) => {
+                if p.remove_pos(&pos) {
+                    self.attach(key, pos, Some((k, p)))
+                } else {
+                    self.0.insert(k, p);
+                    self.attach(key, pos, None)
+                }
+            }
+            None => self.attach(key, pos, None),
+        }
     }
 }
 
 /// Implementation for extending the [`Retriever`].
 #[repr(transparent)]
-pub struct HashStoreExt<K, P>(HashStore<K, P>);
+pub struct HashStoreExt<K, P, H>(HashStore<K, P, H>);
 
-impl<K, P> Deref for HashStore<K, P> {
-    type Target = HashStoreExt<K, P>;
+impl<K, P, H> Deref for HashStore<K, P, H> {
+    type Target = HashStoreExt<K, P, H>;
 
     fn deref(&self) -> &Self::Target {
         // SAFTY:
         // self is a valid pointer and
         // HashStoreExt is repr(transparent) thus has the same memory layout like HashStore
-        unsafe { &*(self as *const HashStore<K, P> as *const HashStoreExt<K, P>) }
+        unsafe { &*(self as *const HashStore<K, P, H> as *const HashStoreExt<K, P, H>) }
     }
 }
 
-impl<K, P> HashStoreExt<K, P> {
+impl<K, P, H> HashStoreExt<K, P, H> {
     pub fn keys(&self) -> impl Iterator<Item = &'_ K> {
         self.0 .0.keys()
     }
+
+    /// Returns a reference to the `BuildHasher` of the `Store`.
+    pub fn hasher(&self) -> &H {
+        self.0 .0.hasher()
+    }
 }
 
 #[cfg(test)]
@@ -178,6 +409,168 @@ mod tests {
         assert!(keys.contains(&&String::from("s")));
     }
 
+    #[test]
+    fn insert_many() {
+        use crate::lookup::store::position::MultiKeyPosition;
+
+        let mut idx = HashStore::<&str, MultiKeyPosition<usize>>::with_capacity(0);
+        idx.insert("a", 3);
+        idx.insert_many([("b", 5), ("a", 4), ("b", 1), ("a", 0), ("a", 4)]);
+
+        assert_eq!(&[0, 3, 4], idx.pos_by_key("a"));
+        assert_eq!(&[1, 5], idx.pos_by_key("b"));
+
+        // after insert many, insert must be work
+        idx.insert("a", 2);
+        assert_eq!(&[0, 2, 3, 4], idx.pos_by_key("a"));
+
+        // a new Key and an existing Key in one batch
+        idx.insert_many([("c", 9), ("b", 3), ("c", 7)]);
+        assert_eq!(&[1, 3, 5], idx.pos_by_key("b"));
+        assert_eq!(&[7, 9], idx.pos_by_key("c"));
+        assert_eq!(&[0, 2, 3, 4], idx.pos_by_key("a"));
+    }
+
+    #[test]
+    fn custom_key_position() {
+        // a KeyPosition without merge, which keeps only the last Position
+        #[derive(Debug)]
+        struct Last(usize);
+
+        impl KeyPosition for Last {
+            type Pos = usize;
+
+            fn from_pos(pos: usize) -> Self {
+                Last(pos)
+            }
+
+            fn add_pos(&mut self, pos: usize) {
+                self.0 = pos;
+            }
+
+            fn remove_pos(&mut self, pos: &usize) -> bool {
+                self.0 == *pos
+            }
+        }
+
+        let mut idx = HashStore::<&str, Last>::with_capacity(0);
+        idx.insert_many([("a", 1), ("b", 2)]);
+        idx.insert_many([("a", 3), ("c", 4)]);
+
+        assert_eq!(3, idx.0["a"].0);
+        assert_eq!(2, idx.0["b"].0);
+        assert_eq!(4, idx.0["c"].0);
+    }
+
+    #[test]
+    fn update() {
+        let mut idx = UniqueKeyHash::with_capacity(5);
+        idx.insert(String::from("a"), 0);
+        idx.insert(String::from("b"), 1);
+
+        // key not changed
+        idx.update(String::from("a"), 0, String::from("a"));
+        assert_eq!(&[0], idx.pos_by_key("a"));
+
+        // key changed
+        idx.update(String::from("a"), 0, String::from("c"));
+        assert!(!idx.key_exist("a"));
+        assert_eq!(&[0], idx.pos_by_key("c"));
+        assert_eq!(&[1], idx.pos_by_key("b"));
+    }
+
+    #[test]
+    fn detach_and_attach() {
+        use crate::lookup::store::position::MultiKeyPosition;
+
+        let mut idx = HashStore::<String, MultiKeyPosition<usize>>::with_capacity(0);
+        idx.attach("a", 0, None);
+        idx.attach("a", 1, None);
+        idx.attach("b", 2, None);
+        assert_eq!(&[0, 1], idx.pos_by_key("a"));
+
+        // not the last pos
+        assert_eq!(None, idx.detach("a", &0));
+        assert_eq!(&[1], idx.pos_by_key("a"));
+
+        // the last pos, reuse the same key
+        let detached = idx.detach("b", &2);
+        assert_eq!(Some((String::from("b"), vec![])), detached);
+        assert!(!idx.key_exist("b"));
+        idx.attach("b", 3, detached);
+        assert_eq!(&[3], idx.pos_by_key("b"));
+
+        // the last pos, the key is changed
+        let detached = idx.detach("b", &3);
+        idx.attach("c", 3, detached);
+        assert!(!idx.key_exist("b"));
+        assert_eq!(&[3], idx.pos_by_key("c"));
+
+        // key exist, detached is ignored
+        let detached = idx.detach("c", &3);
+        idx.attach("a", 3, detached);
+        assert!(!idx.key_exist("c"));
+        assert_eq!(&[1, 3], idx.pos_by_key("a"));
+
+        // not existing key or pos
+        assert_eq!(None, idx.detach("z", &0));
+        assert_eq!(None, idx.detach("a", &99));
+    }
+
+    #[test]
+    fn take_and_restore_entry() {
+        use crate::lookup::store::position::MultiKeyPosition;
+
+        let mut idx = HashStore::<String, MultiKeyPosition<usize>>::with_capacity(0);
+        idx.insert_many([
+            (String::from("a"), 0),
+            (String::from("a"), 1),
+            (String::from("b"), 2),
+        ]);
+
+        // the key is not changed, the entry is the same
+        let taken = idx.take_entry("a", &0);
+        let ptr = taken.as_ref().unwrap().1.as_ptr();
+        assert!(!idx.key_exist("a"));
+        idx.restore_entry("a", 0, taken);
+        assert_eq!(&[0, 1], idx.pos_by_key("a"));
+        assert_eq!(ptr, idx.pos_by_key("a").as_ptr());
+
+        // the key is changed, not the last pos
+        let taken = idx.take_entry("a", &0);
+        idx.restore_entry("b", 0, taken);
+        assert_eq!(&[1], idx.pos_by_key("a"));
+        assert_eq!(&[0, 2], idx.pos_by_key("b"));
+
+        // the key is changed, the last pos
+        let taken = idx.take_entry("a", &1);
+        idx.restore_entry("c", 1, taken);
+        assert!(!idx.key_exist("a"));
+        assert_eq!(&[1], idx.pos_by_key("c"));
+
+        // not existing key
+        let taken = idx.take_entry("z", &3);
+        idx.restore_entry("z", 3, taken);
+        assert_eq!(&[3], idx.pos_by_key("z"));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn merge() {
+        use crate::lookup::store::position::MultiKeyPosition;
+
+        let mut idx = HashStore::<&str, MultiKeyPosition<usize>>::with_capacity(0);
+        idx.insert_many([("a", 3), ("b", 1)]);
+
+        let mut other = HashStore::<&str, MultiKeyPosition<usize>>::with_capacity(0);
+        other.insert_many([("a", 0), ("c", 2), ("a", 4)]);
+
+        idx.merge(other);
+        assert_eq!(&[0, 3, 4], idx.pos_by_key("a"));
+        assert_eq!(&[1], idx.pos_by_key("b"));
+        assert_eq!(&[2], idx.pos_by_key("c"));
+    }
+
     #[test]
     fn store_and_lookup() {
         let mut idx = UniqueKeyHash::with_capacity(5);
@@ -209,4 +602,37 @@ mod tests {
         assert!(keys.contains("c"));
         assert!(keys.contains("s"));
     }
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    struct Seeded(u64);
+
+    impl BuildHasher for Seeded {
+        type Hasher = std::collections::hash_map::DefaultHasher;
+
+        fn build_hasher(&self) -> Self::Hasher {
+            use std::hash::Hasher;
+
+            let mut hasher = Self::Hasher::default();
+            hasher.write_u64(self.0);
+            hasher
+        }
+    }
+
+    #[test]
+    fn with_hasher() {
+        let lookup = HashLookup::<String, UniqueKeyPosition<usize>>::new().with_hasher(Seeded(42));
+
+        let mut idx = lookup.new_store(3);
+        assert_eq!(&Seeded(42), idx.0.hasher());
+
+        idx.insert(String::from("a"), 0);
+        idx.insert(String::from("b"), 1);
+        assert_eq!(&[1], idx.pos_by_key("b"));
+
+        // the view is using the same hasher
+        let view = idx.create_view([String::from("b")]);
+        assert_eq!(&Seeded(42), view.hasher());
+        assert!(view.key_exist("b"));
+        assert!(!view.key_exist("a"));
+    }
 }
@@ -14,9 +14,11 @@
 //!
 //! The finding of an `Key` is very fast (you can __directly__ jump to the `Key`)
 //!
+#[cfg(feature = "rayon")]
+use crate::lookup::store::{position::MergeKeyPosition, MergeStore};
 use crate::lookup::store::{
     position::{KeyPosition, KeyPositionAsSlice},
-    Lookup, Positions, Retriever, Store, View, ViewCreator,
+    Groups, Lookup, Positions, Retriever, Store, View, ViewCreator,
 };
 use std::{marker::PhantomData, ops::Deref};
 
@@ -36,7 +38,7 @@ where
 /// `Key` is from type [`usize`] and the information are saved in a List (Store).
 #[derive(Debug, Clone)]
 #[repr(transparent)]
-pub struct IndexStore<K, P>(Vec<Option<(K, P)>>);
+pub struct IndexStore<K, P>(pub(crate) Vec<Option<(K, P)>>);
 
 impl<K, P> Retriever<K> for IndexStore<K, P>
 where
@@ -55,6 +57,22 @@ where
             _ => &[],
         }
     }
+
+    // an empty Store has no Keys, so the given keys must not be checked
+    fn count_existing_keys<It>(&self, keys: It) -> usize
+    where
+        It: IntoIterator<Item = K>,
+    {
+        if self.0.is_empty() {
+            return 0;
+        }
+
+        let len = self.0.len();
+        keys.into_iter()
+            .map(Into::into)
+            .filter(|idx| *idx < len && self.0[*idx].is_some())
+            .count()
+    }
 }
 
 impl<'a, K, P> ViewCreator<'a> for IndexStore<K, P>
@@ -83,7 +101,7 @@ where
     }
 }
 
-impl<'a, K, P> Positions for IndexStore<K, &'a P>
+impl<K, P> Positions for IndexStore<K, P>
 where
     P: KeyPositionAsSlice,
 {
@@ -97,6 +115,21 @@ where
     }
 }
 
+/// The `Key`s are in ascending order.
+impl<K, P> Groups for IndexStore<K, P>
+where
+    P: KeyPositionAsSlice,
+{
+    type Key = K;
+    type Pos = P::Pos;
+
+    fn groups(&self) -> impl Iterator<Item = (&'_ K, &'_ [P::Pos])> {
+        self.0
+            .iter()
+            .filter_map(|o| o.as_ref().map(|(k, p)| (k, p.as_position_slice())))
+    }
+}
+
 impl<K, P> Store for IndexStore<K, P>
 where
     K: Into<usize> + Clone,
@@ -122,9 +155,178 @@ where
         }
     }
 
+    fn insert_many<It>(&mut self, it: It)
+    where
+        It: IntoIterator<Item = (Self::Key, Self::Pos)>,
+    {
+        let items = it
+            .into_iter()
+            .map(|(key, pos)| (key.clone().into(), key, pos))
+            .collect::<Vec<_>>();
+
+        // resize the vec only ones, with the greatest index
+        if let Some(max) = items.iter().map(|(idx, _, _)| *idx).max() {
+            if self.0.len() <= max {
+                self.0.resize(max + 1, None);
+            }
+        }
+
+        // only the Keys with more than one Position must be normalized
+        let mut touched = Vec::new();
+        items
+            .into_iter()
+            .for_each(|(idx, key, pos)| match self.0[idx] {
+                Some((_, ref mut p)) => {
+                    p.push_pos(pos);
+                    touched.push(idx);
+                }
+                None => self.0[idx] = Some((key, P::from_pos(pos))),
+            });
+
+        touched.sort_unstable();
+        touched.dedup();
+        touched.into_iter().for_each(|idx| {
+            if let Some((_, p)) = &mut self.0[idx] {
+                p.normalize()
+            }
+        });
+    }
+
+    /// If the index of the `Key` is not changed, the `Store` is not touched.
+    fn update(&mut self, old_key: Self::Key, pos: Self::Pos, new_key: Self::Key) {
+        let old_idx = old_key.into();
+        if old_idx != new_key.clone().into() {
+            self.delete_by_index(old_idx, &pos);
+            self.insert(new_key, pos);
+        }
+    }
+
     fn delete(&mut self, key: Self::Key, pos: &Self::Pos) {
-        let idx = key.into();
+        self.delete_by_index(key.into(), pos)
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional)
+    }
+
+    fn clear(&mut self) {
+        self.0.clear()
+    }
+}
 
+#[cfg(feature = "rayon")]
+impl<K, P> MergeStore for IndexStore<K, P>
+where
+    K: Into<usize> + Clone + Send,
+    P: MergeKeyPosition + Clone + Send,
+{
+    fn merge(&mut self, other: Self) {
+        if self.0.len() < other.0.len() {
+            self.0.resize(other.0.len(), None);
+        }
+
+        other
+            .0
+            .into_iter()
+            .enumerate()
+            .for_each(|(idx, o)| match (&mut self.0[idx], o) {
+                (_, None) => {}
+                (Some((_, p)), Some((_, pos))) => p.merge(pos),
+                (slot, other) => *slot = other,
+            });
+    }
+
+    /// The `Store` is created only once, with the greatest index of all `Key`s.
+    /// The `Key`s are created once and partitioned by disjoint ranges of indices,
+    /// so every thread fills only its own range (no `Store` per chunk and no `merge` are necessary).
+    fn par_new_list<N, F, I>(_new_store: N, field: &F, items: &[I]) -> Self
+    where
+        N: Fn(usize) -> Self + Sync,
+        F: Fn(&I) -> Self::Key + Sync,
+        I: Sync,
+        Self: Send,
+        Self::Pos: From<usize>,
+    {
+        use rayon::prelude::*;
+
+        const MIN_RANGE_SIZE: usize = 1_024;
+        let threads = rayon::current_num_threads();
+
+        // create the Keys with the indices for contiguous chunks of Items
+        let chunk_size = items.len().div_ceil(threads).max(MIN_RANGE_SIZE);
+        let chunks = items
+            .par_chunks(chunk_size)
+            .map(|chunk| {
+                let mut len = 0;
+                let keys = chunk
+                    .iter()
+                    .map(|item| {
+                        let key = field(item);
+                        let idx = key.clone().into();
+                        len = len.max(idx + 1);
+                        (idx, key)
+                    })
+                    .collect::<Vec<(usize, K)>>();
+                (len, keys)
+            })
+            .collect::<Vec<_>>();
+
+        let len = chunks.iter().map(|(len, _)| *len).max().unwrap_or(0);
+        let range_size = len.div_ceil(threads).max(MIN_RANGE_SIZE);
+        let range_count = len.div_ceil(range_size);
+
+        // partition every chunk by the ranges of the indices
+        let buckets = chunks
+            .into_par_iter()
+            .enumerate()
+            .map(|(n, (_, chunk))| {
+                let mut buckets = (0..range_count).map(|_| Vec::new()).collect::<Vec<_>>();
+                for (i, (idx, key)) in chunk.into_iter().enumerate() {
+                    buckets[idx / range_size].push((n * chunk_size + i, idx, key));
+                }
+                buckets
+            })
+            .collect::<Vec<_>>();
+
+        // per range all buckets in the order of the chunks, so the positions are ascending
+        let mut ranges = (0..range_count)
+            .map(|_| Vec::with_capacity(buckets.len()))
+            .collect::<Vec<_>>();
+        for chunk in buckets {
+            for (range, bucket) in ranges.iter_mut().zip(chunk) {
+                range.push(bucket);
+            }
+        }
+
+        let mut store = Self(Vec::new());
+        store.0.resize_with(len, || None);
+        store
+            .0
+            .par_chunks_mut(range_size)
+            .zip(ranges)
+            .enumerate()
+            .for_each(|(n, (range, buckets))| {
+                let start = n * range_size;
+                for (pos, idx, key) in buckets.into_iter().flatten() {
+                    match &mut range[idx - start] {
+                        Some((_, p)) => p.push_pos(pos.into()),
+                        slot => *slot = Some((key, P::from_pos(pos.into()))),
+                    }
+                }
+            });
+        store
+    }
+}
+
+impl<K, P> IndexStore<K, P>
+where
+    P: KeyPosition,
+{
+    fn delete_by_index(&mut self, idx: usize, pos: &P::Pos) {
         if let Some(Some((_, rm_idx))) = self.0.get_mut(idx) {
             if rm_idx.remove_pos(pos) {
                 // las pos was deleted
@@ -132,10 +334,6 @@ where
             }
         }
     }
-
-    fn with_capacity(capacity: usize) -> Self {
-        Self(Vec::with_capacity(capacity))
-    }
 }
 
 /// A proxy for exposing [`IndexStore`] specific extensions.
@@ -392,6 +590,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn insert_many() {
+        let mut idx = MultiKeyIndex::<usize>::with_capacity(0);
+        idx.insert(1, 3);
+        idx.insert_many([(2, 5), (1, 4), (2, 1), (1, 0), (1, 4), (7, 6)]);
+
+        assert_eq!(&[0, 3, 4], idx.pos_by_key(1));
+        assert_eq!(&[1, 5], idx.pos_by_key(2));
+        assert_eq!(&[6], idx.pos_by_key(7));
+        assert_eq!(Some(7), idx.max_key());
+
+        // after insert many, insert must be work
+        idx.insert(1, 2);
+        assert_eq!(&[0, 2, 3, 4], idx.pos_by_key(1));
+
+        // nothing to insert
+        idx.insert_many([]);
+        assert_eq!(vec![1, 2, 7], idx.keys().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn update() {
+        let mut idx = MultiKeyIndex::<usize>::with_capacity(0);
+        idx.insert_many([(1, 3), (1, 1), (2, 0)]);
+
+        // key not changed
+        idx.update(1, 3, 1);
+        assert_eq!(&[1, 3], idx.pos_by_key(1));
+
+        // key changed
+        idx.update(1, 3, 2);
+        assert_eq!(&[1], idx.pos_by_key(1));
+        assert_eq!(&[0, 3], idx.pos_by_key(2));
+
+        // last pos for key 1
+        idx.update(1, 1, 5);
+        assert!(!idx.key_exist(1));
+        assert_eq!(vec![2, 5], idx.keys().collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn merge() {
+        let mut idx = MultiKeyIndex::<usize>::with_capacity(0);
+        idx.insert_many([(1, 3), (2, 1)]);
+
+        let mut other = MultiKeyIndex::<usize>::with_capacity(0);
+        other.insert_many([(1, 0), (7, 2), (1, 4)]);
+
+        idx.merge(other);
+        assert_eq!(&[0, 3, 4], idx.pos_by_key(1));
+        assert_eq!(&[1], idx.pos_by_key(2));
+        assert_eq!(&[2], idx.pos_by_key(7));
+        assert_eq!(vec![1, 2, 7], idx.keys().collect::<Vec<_>>());
+    }
+
     #[test]
     fn store_and_lookup() {
         let mut idx = UniqueKeyIndex::with_capacity(5);
@@ -464,4 +718,85 @@ mod tests {
 
         // check many keys
     }
+
+    #[test]
+    fn groups() {
+        let mut idx = MultiKeyIndex::<usize>::with_capacity(0);
+        idx.insert_many([(5, 0), (1, 1), (5, 2), (3, 3)]);
+
+        assert_eq!(
+            vec![(&1, &[1][..]), (&3, &[3][..]), (&5, &[0, 2][..])],
+            idx.groups().collect::<Vec<_>>()
+        );
+
+        let view = idx.create_view([5, 1]);
+        assert_eq!(
+            vec![(&1, &[1][..]), (&5, &[0, 2][..])],
+            view.groups().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn positions_ordered_by_key() {
+        let mut idx = MultiKeyIndex::<u8>::with_capacity(0);
+        idx.insert(5, 0);
+        idx.insert(1, 1);
+        idx.insert(5, 2);
+        idx.insert(3, 3);
+
+        assert_eq!(vec![&1, &3, &0, &2], idx.positions().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn count_and_missing_many_keys() {
+        let mut idx = MultiKeyIndex::<u8>::with_capacity(0);
+        assert_eq!(0, idx.count_existing_keys([0, 1]));
+        assert_eq!(0, idx.count_by_many_keys([0, 1]));
+
+        idx.insert(5, 0);
+        idx.insert(1, 1);
+        idx.insert(5, 2);
+
+        assert_eq!(2, idx.count_existing_keys([1, 2, 5, 100]));
+        assert_eq!(3, idx.count_by_many_keys([1, 2, 5, 100]));
+        assert_eq!(
+            vec![2, 100],
+            idx.missing_keys([1, 2, 5, 100]).collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_new_list() {
+        let items = (0..20_000usize).map(|n| n % 3_000).collect::<Vec<_>>();
+
+        let mut idx = MultiKeyIndex::<usize>::with_capacity(0);
+        idx.insert_many(items.iter().enumerate().map(|(pos, key)| (*key, pos)));
+
+        // more than one range of indices
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        let par_idx = pool.install(|| {
+            MultiKeyIndex::<usize>::par_new_list(
+                MultiKeyIndex::with_capacity,
+                &|n: &usize| *n,
+                &items,
+            )
+        });
+
+        assert_eq!(3_000, par_idx.0.len());
+        assert_eq!(
+            idx.groups().collect::<Vec<_>>(),
+            par_idx.groups().collect::<Vec<_>>()
+        );
+
+        let empty = MultiKeyIndex::<usize>::par_new_list(
+            MultiKeyIndex::with_capacity,
+            &|n: &usize| *n,
+            &[],
+        );
+        assert!(empty.0.is_empty());
+    }
 }
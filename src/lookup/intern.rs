@@ -0,0 +1,636 @@
+//! The `intern` module contains a lookup, which saves every `Key` only once in a [`SymbolTable`].
+//! The `Key`s are replaced by small ids ([`Symbol`]s), which are used in an [`IndexStore`].
+//!
+//! This lookup is well suited for __many__ `Item`s with __few__ different `Key`s (e.g. city names),
+//! which are expensive to clone (e.g. `String`s).
+//! It is an opt-in alternative to the [`crate::HashLookup`], the `HashStore` itself does not intern the `Key`s.
+//!
+//! ### Advantages:
+//! - a `View` shares the `SymbolTable` with the `Store`, no `Key` is cloned by creating a `View`
+//! - the lookup with a `Symbol` is very fast (like the [`IndexStore`])
+//!
+//! ### Disadvantage:
+//! - a `Key` without a `Position` stays in the `SymbolTable`, until the `Store` is compacted
+//!   (see: [`InternStore::compact`]), which renumbers the `Symbol`s
+//!
+#[cfg(feature = "rayon")]
+use crate::lookup::store::{position::MergeKeyPosition, MergeStore};
+use crate::lookup::{
+    index::IndexStore,
+    store::{
+        position::{KeyPosition, KeyPositionAsSlice},
+        BorrowedStore, Groups, Lookup, Positions, Retriever, Store, View, ViewCreator,
+    },
+};
+use std::{
+    borrow::Borrow,
+    hash::Hash,
+    marker::PhantomData,
+    ops::{Deref, Index},
+};
+
+#[cfg(feature = "hashbrown")]
+type HashMap<K, V> = hashbrown::HashMap<K, V>;
+
+#[cfg(not(feature = "hashbrown"))]
+type HashMap<K, V> = std::collections::HashMap<K, V>;
+
+/// `InternLookup` is the creator for the `InternStore`: `Retriever` and `Store`.
+///
+/// # Example
+///
+/// ```
+/// use lookups::{Borrowed, InternLookup, LkupVec, Lookup};
+///
+/// let mut vec = LkupVec::new(
+///     InternLookup::with_multi_keys(),
+///     Borrowed::new(|city: &(usize, String)| city.1.as_str()),
+/// );
+///
+/// vec.push((1, "Berlin".into()));
+/// vec.push((2, "Paris".into()));
+/// vec.push((3, "Berlin".into()));
+///
+/// assert_eq!(2, vec.get_by_lkup_key("Berlin").count());
+///
+/// // the view borrows the symbol table, no Key is cloned
+/// let view = vec.create_lkup_view([String::from("Paris")]);
+/// assert!(view.contains_key("Paris"));
+/// assert!(!view.contains_key("Berlin"));
+/// ```
+pub struct InternLookup<K, P>(PhantomData<K>, PhantomData<P>);
+
+impl<K, P> Lookup<InternStore<K, P>, P> for InternLookup<K, P>
+where
+    K: Hash + Eq + Clone,
+    P: KeyPosition + Clone,
+{
+    fn new() -> Self {
+        Self(PhantomData, PhantomData)
+    }
+}
+
+/// A `Symbol` is a small id, which replaced the `Key`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Symbol(u32);
+
+impl From<Symbol> for usize {
+    fn from(sym: Symbol) -> Self {
+        sym.0 as usize
+    }
+}
+
+/// The `SymbolTable` saves every `Key` once and maps the `Key` to a [`Symbol`].
+#[derive(Debug, Clone)]
+pub struct SymbolTable<K> {
+    symbols: HashMap<K, Symbol>,
+    keys: Vec<K>,
+}
+
+impl<K> Default for SymbolTable<K> {
+    fn default() -> Self {
+        Self {
+            symbols: HashMap::default(),
+            keys: Vec::default(),
+        }
+    }
+}
+
+impl<K> SymbolTable<K> {
+    /// Returns the `Symbol` for the given `Key`, if the `Key` exist.
+    pub fn get<Q>(&self, key: &Q) -> Option<Symbol>
+    where
+        K: Borrow<Q> + Hash + Eq,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.symbols.get(key).copied()
+    }
+
+    /// Returns the `Key` for the given `Symbol`.
+    pub fn resolve(&self, sym: Symbol) -> Option<&K> {
+        self.keys.get(usize::from(sym))
+    }
+
+    /// Returns the count of saved `Key`s.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true`, if the `SymbolTable` contains no `Key`s.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the `Symbol` for the `Key`. If the `Key` not exist, than is the `Key` saved.
+    ///
+    /// ## Panics
+    /// Panics, if there are more than `u32::MAX` `Key`s.
+    pub fn intern(&mut self, key: K) -> Symbol
+    where
+        K: Hash + Eq + Clone,
+    {
+        if let Some(sym) = self.symbols.get(&key) {
+            return *sym;
+        }
+
+        let sym = self.next_symbol();
+        self.keys.push(key.clone());
+        self.symbols.insert(key, sym);
+        sym
+    }
+
+    /// Like [`SymbolTable::intern`], but an owned `Key` is only created, if the `Key` not exist.
+    pub fn intern_borrowed<Q>(&mut self, key: &Q) -> Symbol
+    where
+        K: Borrow<Q> + Hash + Eq + Clone,
+        Q: ToOwned<Owned = K> + Hash + Eq + ?Sized,
+    {
+        match self.symbols.get(key) {
+            Some(sym) => *sym,
+            None => self.intern(key.to_owned()),
+        }
+    }
+
+    fn next_symbol(&self) -> Symbol {
+        Symbol(u32::try_from(self.keys.len()).expect("too many Symbols for the SymbolTable"))
+    }
+}
+
+impl<K> Index<Symbol> for SymbolTable<K> {
+    type Output = K;
+
+    fn index(&self, sym: Symbol) -> &Self::Output {
+        &self.keys[usize::from(sym)]
+    }
+}
+
+/// `InternStore` is an implementation for an interning index.
+/// The `Store` owns the [`SymbolTable`], a `View` borrows the `SymbolTable` from the `Store`.
+///
+#[derive(Debug, Clone)]
+pub struct InternStore<K, P, T = SymbolTable<K>> {
+    symbols: T,
+    store: IndexStore<Symbol, P>,
+    // count of Symbols, which lost the last Position (since the last compaction)
+    released: usize,
+    _key: PhantomData<K>,
+}
+
+impl<Q, K, P, T> Retriever<&Q> for InternStore<K, P, T>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: Hash + Eq + ?Sized,
+    P: KeyPositionAsSlice,
+    T: Borrow<SymbolTable<K>>,
+{
+    type Pos = P::Pos;
+
+    fn key_exist(&self, key: &Q) -> bool {
+        match self.symbols.borrow().get(key) {
+            Some(sym) => self.store.key_exist(sym),
+            None => false,
+        }
+    }
+
+    fn pos_by_key(&self, key: &Q) -> &[Self::Pos] {
+        match self.symbols.borrow().get(key) {
+            Some(sym) => self.store.pos_by_key(sym),
+            None => &[],
+        }
+    }
+}
+
+impl<'a, K, P> ViewCreator<'a> for InternStore<K, P>
+where
+    K: Hash + Eq + 'a,
+    P: KeyPositionAsSlice + 'a,
+{
+    type Key = K;
+    type Retriever = InternStore<K, &'a P, &'a SymbolTable<K>>;
+
+    fn create_view<It>(&'a self, keys: It) -> View<Self::Retriever>
+    where
+        It: IntoIterator<Item = Self::Key>,
+    {
+        let mut lkup = Vec::new();
+        lkup.resize(self.store.0.len(), None);
+
+        for key in keys {
+            if let Some(sym) = self.symbols.get(&key) {
+                let idx = usize::from(sym);
+                if let Some(Some((_, p))) = self.store.0.get(idx) {
+                    lkup[idx] = Some((sym, p));
+                }
+            }
+        }
+
+        View::new(InternStore {
+            symbols: &self.symbols,
+            store: IndexStore(lkup),
+            released: 0,
+            _key: PhantomData,
+        })
+    }
+}
+
+impl<K, P, T> Positions for InternStore<K, P, T>
+where
+    P: KeyPositionAsSlice,
+{
+    type Pos = P::Pos;
+
+    fn positions(&self) -> impl Iterator<Item = &'_ P::Pos> {
+        self.store.positions()
+    }
+}
+
+impl<K, P, T> Groups for InternStore<K, P, T>
+where
+    P: KeyPositionAsSlice,
+    T: Borrow<SymbolTable<K>>,
+{
+    type Key = K;
+    type Pos = P::Pos;
+
+    fn groups(&self) -> impl Iterator<Item = (&'_ K, &'_ [P::Pos])> {
+        let symbols = self.symbols.borrow();
+        self.store
+            .groups()
+            .map(move |(sym, pos)| (&symbols[*sym], pos))
+    }
+}
+
+impl<K, P> Store for InternStore<K, P>
+where
+    K: Hash + Eq + Clone,
+    P: KeyPosition + Clone,
+{
+    type Key = K;
+    type Pos = P::Pos;
+
+    fn insert(&mut self, key: Self::Key, pos: Self::Pos) {
+        let sym = self.symbols.intern(key);
+        self.store.insert(sym, pos)
+    }
+
+    fn insert_many<It>(&mut self, it: It)
+    where
+        It: IntoIterator<Item = (Self::Key, Self::Pos)>,
+    {
+        let symbols = &mut self.symbols;
+        self.store
+            .insert_many(it.into_iter().map(|(key, pos)| (symbols.intern(key), pos)))
+    }
+
+    fn update(&mut self, old_key: Self::Key, pos: Self::Pos, new_key: Self::Key) {
+        let new_sym = self.symbols.intern(new_key);
+        match self.symbols.get(&old_key) {
+            Some(old_sym) => {
+                self.store.update(old_sym, pos, new_sym);
+                self.release(old_sym);
+            }
+            None => self.store.insert(new_sym, pos),
+        }
+    }
+
+    fn delete(&mut self, key: Self::Key, pos: &Self::Pos) {
+        if let Some(sym) = self.symbols.get(&key) {
+            self.store.delete(sym, pos);
+            self.release(sym);
+        }
+    }
+
+    /// The `capacity` is ignored, because the count of different `Key`s is unknown.
+    fn with_capacity(_capacity: usize) -> Self {
+        Self {
+            symbols: SymbolTable::default(),
+            store: IndexStore::with_capacity(0),
+            released: 0,
+            _key: PhantomData,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.symbols = SymbolTable::default();
+        self.store.clear();
+        self.released = 0;
+    }
+}
+
+impl<K, P> InternStore<K, P>
+where
+    K: Hash + Eq + Clone,
+    P: KeyPosition,
+{
+    /// The minimum count of released `Symbol`s, before the `Store` is compacted automatically.
+    const MIN_RELEASED: usize = 64;
+
+    /// Removes all `Key`s without a `Position` from the [`SymbolTable`].
+    /// The `Symbol`s of the remaining `Key`s are renumbered.
+    ///
+    /// The `Store` is compacted automatically, if at least the half of the `Symbol`s has lost the last `Position`.
+    pub fn compact(&mut self) {
+        let mut keys = std::mem::take(&mut self.symbols)
+            .keys
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+
+        let entries = std::mem::take(&mut self.store.0);
+        self.store.0.reserve(entries.len());
+
+        for (sym, p) in entries.into_iter().flatten() {
+            if let Some(key) = keys[usize::from(sym)].take() {
+                // the new Symbols are ascending, like the old Symbols
+                let sym = self.symbols.intern(key);
+                self.store.0.push(Some((sym, p)));
+            }
+        }
+        self.released = 0;
+    }
+
+    // the `sym` has maybe lost the last `Position`
+    fn release(&mut self, sym: Symbol) {
+        if !matches!(self.store.0.get(usize::from(sym)), Some(Some(_))) {
+            self.released += 1;
+            if self.released >= Self::MIN_RELEASED && self.released * 2 >= self.symbols.len() {
+                self.compact();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, P> MergeStore for InternStore<K, P>
+where
+    K: Hash + Eq + Clone,
+    P: MergeKeyPosition + Clone + Send,
+{
+    fn merge(&mut self, other: Self) {
+        let mut remapped = IndexStore::with_capacity(0);
+        remapped
+            .0
+            .resize(self.symbols.len() + other.symbols.len(), None);
+
+        for (sym, p) in other.store.0.into_iter().flatten() {
+            let sym = self.symbols.intern(other.symbols[sym].clone());
+            remapped.0[usize::from(sym)] = Some((sym, p));
+        }
+
+        self.store.merge(remapped);
+    }
+}
+
+impl<Q, K, P> BorrowedStore<Q> for InternStore<K, P>
+where
+    K: Borrow<Q> + Hash + Eq + Clone,
+    Q: ToOwned<Owned = K> + Hash + Eq + ?Sized,
+    P: KeyPosition + Clone,
+{
+    /// Nothing to reuse, the `Key` is only removed from the `SymbolTable` by [`InternStore::compact`].
+    /// By [`BorrowedStore::take_entry`] the `Symbol` of the old `Key` is returned.
+    type Detached = Symbol;
+
+    fn detach(&mut self, key: &Q, pos: &Self::Pos) -> Option<Self::Detached> {
+        if let Some(sym) = self.symbols.get(key) {
+            self.store.delete(sym, pos);
+            self.release(sym);
+        }
+        None
+    }
+
+    fn attach(&mut self, key: &Q, pos: Self::Pos, _detached: Option<Self::Detached>) {
+        let sym = self.symbols.intern_borrowed(key);
+        self.store.insert(sym, pos)
+    }
+
+    fn take_entry(&mut self, key: &Q, _pos: &Self::Pos) -> Option<Self::Detached> {
+        self.symbols.get(key)
+    }
+
+    /// If the `Symbol` is not changed, the `Store` is not touched.
+    fn restore_entry(&mut self, key: &Q, pos: Self::Pos, taken: Option<Self::Detached>) {
+        let sym = self.symbols.intern_borrowed(key);
+        match taken {
+            Some(old_sym) => {
+                self.store.update(old_sym, pos, sym);
+                self.release(old_sym);
+            }
+            None => self.store.insert(sym, pos),
+        }
+    }
+}
+
+/// A proxy for exposing [`InternStore`] specific extensions.
+#[repr(transparent)]
+pub struct InternStoreExt<K, P, T>(InternStore<K, P, T>);
+
+impl<K, P, T> Deref for InternStore<K, P, T> {
+    type Target = InternStoreExt<K, P, T>;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFTY:
+        // self is a valid pointer and
+        // InternStoreExt is repr(transparent) thus has the same memory layout like InternStore
+        unsafe { &*(self as *const InternStore<K, P, T> as *const InternStoreExt<K, P, T>) }
+    }
+}
+
+impl<K, P, T> InternStoreExt<K, P, T>
+where
+    T: Borrow<SymbolTable<K>>,
+{
+    /// Returns the shared [`SymbolTable`].
+    pub fn symbols(&self) -> &SymbolTable<K> {
+        self.0.symbols.borrow()
+    }
+
+    /// Returns all `Symbol`s, which have at least one `Position`.
+    /// The `Symbol`s are changed by [`InternStore::compact`].
+    pub fn symbol_keys(&self) -> impl Iterator<Item = Symbol> + '_ {
+        self.0.store.keys()
+    }
+
+    /// Returns all `Key`s, which have at least one `Position`.
+    pub fn keys(&self) -> impl Iterator<Item = &'_ K> {
+        self.symbol_keys().map(|sym| &self.symbols()[sym])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lookup::store::position::{MultiKeyPosition, UniqueKeyPosition};
+
+    type MultiKeyIntern<K = String, X = usize> = InternStore<K, MultiKeyPosition<X>>;
+
+    #[test]
+    fn symbol_table() {
+        let mut table = SymbolTable::default();
+        assert!(table.is_empty());
+
+        let a = table.intern(String::from("a"));
+        let b = table.intern_borrowed("b");
+        assert_eq!(a, table.intern_borrowed("a"));
+        assert_eq!(b, table.intern(String::from("b")));
+        assert_eq!(2, table.len());
+
+        assert_eq!(Some(a), table.get("a"));
+        assert_eq!(None, table.get("z"));
+        assert_eq!(Some(&String::from("b")), table.resolve(b));
+        assert_eq!("a", &table[a]);
+    }
+
+    #[test]
+    fn store_and_lookup() {
+        let mut idx = MultiKeyIntern::with_capacity(10);
+        idx.insert_many([
+            (String::from("Berlin"), 0),
+            (String::from("Paris"), 1),
+            (String::from("Berlin"), 3),
+        ]);
+        idx.insert(String::from("Rom"), 2);
+
+        assert!(idx.key_exist("Berlin"));
+        assert!(!idx.key_exist("London"));
+        assert_eq!(&[0, 3], idx.pos_by_key("Berlin"));
+        assert_eq!(&[2], idx.pos_by_key("Rom"));
+        assert_eq!(&[0usize; 0], idx.pos_by_key("London"));
+
+        assert_eq!(
+            vec!["Berlin", "Paris", "Rom"],
+            idx.keys().collect::<Vec<_>>()
+        );
+
+        // the key stays in the symbol table
+        idx.delete(String::from("Paris"), &1);
+        assert!(!idx.key_exist("Paris"));
+        assert_eq!(vec!["Berlin", "Rom"], idx.keys().collect::<Vec<_>>());
+        assert_eq!(3, idx.symbols().len());
+
+        // reuse the symbol
+        idx.update(String::from("Rom"), 2, String::from("Paris"));
+        assert!(!idx.key_exist("Rom"));
+        assert_eq!(&[2], idx.pos_by_key("Paris"));
+        assert_eq!(3, idx.symbols().len());
+
+        idx.clear();
+        assert!(!idx.key_exist("Berlin"));
+        assert!(idx.symbols().is_empty());
+    }
+
+    #[test]
+    fn compact() {
+        let mut idx = MultiKeyIntern::with_capacity(0);
+        idx.insert_many([
+            (String::from("a"), 0),
+            (String::from("b"), 1),
+            (String::from("c"), 2),
+            (String::from("b"), 3),
+        ]);
+        idx.delete(String::from("a"), &0);
+        idx.update(String::from("c"), 2, String::from("b"));
+        assert_eq!(3, idx.symbols().len());
+
+        idx.compact();
+        assert_eq!(1, idx.symbols().len());
+        assert_eq!(&[1, 2, 3], idx.pos_by_key("b"));
+        assert!(!idx.key_exist("a"));
+        assert_eq!(vec!["b"], idx.keys().collect::<Vec<_>>());
+
+        // the compacted Store can be used further
+        idx.insert(String::from("a"), 4);
+        assert_eq!(&[4], idx.pos_by_key("a"));
+        assert_eq!(2, idx.symbols().len());
+    }
+
+    #[test]
+    fn compact_by_churning_keys() {
+        let mut idx = MultiKeyIntern::with_capacity(0);
+        idx.insert(String::from("stable"), 0);
+
+        for n in 0..1_000 {
+            idx.insert(format!("key-{n}"), 1);
+            idx.delete(format!("key-{n}"), &1);
+        }
+
+        // the released Symbols are removed (automatically)
+        assert!(idx.symbols().len() <= 2 * MultiKeyIntern::<String, usize>::MIN_RELEASED);
+        assert_eq!(&[0], idx.pos_by_key("stable"));
+        assert_eq!(vec!["stable"], idx.keys().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn create_view() {
+        let mut idx = InternStore::<String, UniqueKeyPosition<usize>>::with_capacity(0);
+        idx.insert(String::from("a"), 0);
+        idx.insert(String::from("b"), 1);
+        idx.insert(String::from("c"), 2);
+
+        let view = idx.create_view([String::from("b"), String::from("c"), String::from("z")]);
+        assert!(!view.key_exist("a"));
+        assert!(view.key_exist("b"));
+        assert_eq!(&[2], view.pos_by_key("c"));
+        assert_eq!(vec![&1, &2], view.positions().collect::<Vec<_>>());
+
+        // the view shares the symbol table
+        assert!(std::ptr::eq(idx.symbols(), view.symbols()));
+        assert_eq!(vec!["b", "c"], view.keys().collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn merge() {
+        let mut idx = MultiKeyIntern::with_capacity(0);
+        idx.insert_many([(String::from("a"), 3), (String::from("b"), 1)]);
+
+        let mut other = MultiKeyIntern::with_capacity(0);
+        other.insert_many([
+            (String::from("c"), 2),
+            (String::from("a"), 0),
+            (String::from("a"), 4),
+        ]);
+
+        idx.merge(other);
+        assert_eq!(&[0, 3, 4], idx.pos_by_key("a"));
+        assert_eq!(&[1], idx.pos_by_key("b"));
+        assert_eq!(&[2], idx.pos_by_key("c"));
+        assert_eq!(3, idx.symbols().len());
+    }
+
+    #[test]
+    fn detach_and_attach() {
+        let mut idx = MultiKeyIntern::with_capacity(0);
+        idx.attach("a", 0, None);
+        idx.attach("a", 1, None);
+
+        assert_eq!(None, idx.detach("a", &0));
+        assert_eq!(&[1], idx.pos_by_key("a"));
+
+        idx.detach("a", &1);
+        assert!(!idx.key_exist("a"));
+
+        idx.attach("a", 2, None);
+        assert_eq!(&[2], idx.pos_by_key("a"));
+        assert_eq!(1, idx.symbols().len());
+    }
+
+    #[test]
+    fn groups() {
+        let mut idx = MultiKeyIntern::with_capacity(0);
+        idx.insert_many([
+            (String::from("b"), 0),
+            (String::from("a"), 1),
+            (String::from("b"), 2),
+        ]);
+
+        assert_eq!(
+            vec![
+                (&String::from("b"), &[0, 2][..]),
+                (&String::from("a"), &[1][..])
+            ],
+            idx.groups().collect::<Vec<_>>()
+        );
+    }
+}
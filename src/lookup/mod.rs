@@ -13,6 +13,17 @@
 //! - hashing based lookup (the implementaion is a `HashMap`)  (e.g: [`hash::HashStore`])
 //! - index base lookup (the lookup carried out by the Index from a `Vec`) (e.g: [`index::IndexStore`])
 //!
+//! and an interning lookup, which saves every `Key` only once in a symbol table (e.g: [`intern::InternStore`])
+//! and for read only collections a frozen hashing lookup (e.g: [`frozen::FrozenHashStore`])
+//! and a sorted lookup (e.g: [`sorted::SortedVecStore`]).
+//!
+//! The [`aggregate::AggregateStore`] maintains for every `Key` the aggregates (count, sum, min, max) of a value.
+//!
+pub mod aggregate;
+pub mod field;
+pub mod frozen;
 pub mod hash;
 pub mod index;
+pub mod intern;
+pub mod sorted;
 pub mod store;
@@ -0,0 +1,424 @@
+//! The `sorted` module contains a lookup implementation, where all `Key`s are saved in a sorted `Vec`.
+//! A `Key` is found by a binary search.
+//!
+//! ### Advantages:
+//! - compact, small memory footprint (well suited for read only collections with a few thousand `Item`s)
+//! - the `Key`s are ordered: `min_key`, `max_key` and range queries are supported
+//!
+//! ### Disadvantages:
+//! - inserting or removing a single `Key` is expensive, the `Vec` must be moved
+//!
+#[cfg(feature = "rayon")]
+use crate::lookup::store::{position::MergeKeyPosition, MergeStore};
+use crate::lookup::store::{
+    position::{KeyPosition, KeyPositionAsSlice},
+    Groups, Lookup, Positions, Retriever, Store, View, ViewCreator,
+};
+#[cfg(feature = "rayon")]
+use std::cmp::Ordering;
+use std::{
+    borrow::Borrow,
+    marker::PhantomData,
+    ops::{Bound, Deref, RangeBounds},
+};
+
+/// `SortedLookup` is the creator for the `SortedVecStore`: `Retriever` and `Store`.
+///
+/// # Example
+///
+/// ```
+/// use lookups::{collections::list::ro::LkupList, lookup::sorted::SortedLookup, Lookup};
+///
+/// let list = LkupList::new(SortedLookup::with_unique_key(), |id: &usize| *id, [7, 1, 4]);
+///
+/// assert!(list.contains_lkup_key(&4));
+/// assert_eq!(Some(&1), list.lkup_ext().min_key());
+/// assert_eq!(Some(&7), list.lkup_ext().max_key());
+///
+/// // the Positions (the index in the list) for all Keys in the range
+/// assert_eq!(vec![&1, &2], list.lkup_ext().range(1..5).collect::<Vec<_>>());
+/// ```
+pub struct SortedLookup<K, P>(PhantomData<K>, PhantomData<P>);
+
+impl<K, P> Lookup<SortedVecStore<K, P>, P> for SortedLookup<K, P>
+where
+    K: Ord,
+    P: KeyPosition,
+{
+    fn new() -> Self {
+        Self(PhantomData, PhantomData)
+    }
+}
+
+/// `SortedVecStore` is an implementation for a sorted index (sorted by the `Key`).
+///
+#[derive(Debug, Clone)]
+#[repr(transparent)]
+pub struct SortedVecStore<K, P>(Vec<(K, P)>);
+
+impl<K, P> SortedVecStore<K, P> {
+    fn find<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.0.binary_search_by(|(k, _)| k.borrow().cmp(key))
+    }
+
+    fn get<Q>(&self, key: &Q) -> Option<&P>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let idx = self.find(key).ok()?;
+        Some(&self.0[idx].1)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, P> SortedVecStore<K, P>
+where
+    K: Ord,
+    P: MergeKeyPosition,
+{
+    // merge two sorted `Vec`s, the `other` must contains unique `Key`s
+    fn merge_sorted(&mut self, other: Vec<(K, P)>) {
+        if self.0.is_empty() {
+            self.0 = other;
+            return;
+        }
+
+        let mut entries = Vec::with_capacity(self.0.len() + other.len());
+        let mut left = std::mem::take(&mut self.0).into_iter().peekable();
+        let mut right = other.into_iter().peekable();
+
+        while let (Some((l, _)), Some((r, _))) = (left.peek(), right.peek()) {
+            match l.cmp(r) {
+                Ordering::Less => entries.extend(left.next()),
+                Ordering::Greater => entries.extend(right.next()),
+                Ordering::Equal => {
+                    if let (Some((key, mut p)), Some((_, other))) = (left.next(), right.next()) {
+                        p.merge(other);
+                        entries.push((key, p));
+                    }
+                }
+            }
+        }
+
+        entries.extend(left);
+        entries.extend(right);
+        self.0 = entries;
+    }
+}
+
+impl<Q, K, P> Retriever<&Q> for SortedVecStore<K, P>
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+    P: KeyPositionAsSlice,
+{
+    type Pos = P::Pos;
+
+    fn key_exist(&self, key: &Q) -> bool {
+        self.find(key).is_ok()
+    }
+
+    fn pos_by_key(&self, key: &Q) -> &[Self::Pos] {
+        match self.get(key) {
+            Some(p) => p.as_position_slice(),
+            None => &[],
+        }
+    }
+}
+
+impl<'a, K, P> ViewCreator<'a> for SortedVecStore<K, P>
+where
+    K: Ord + Clone,
+    P: KeyPositionAsSlice + 'a,
+{
+    type Key = K;
+    type Retriever = SortedVecStore<K, &'a P>;
+
+    fn create_view<It>(&'a self, keys: It) -> View<Self::Retriever>
+    where
+        It: IntoIterator<Item = Self::Key>,
+    {
+        // the indices are in the same order like the Keys
+        let mut indices = keys
+            .into_iter()
+            .filter_map(|key| self.find(&key).ok())
+            .collect::<Vec<_>>();
+        indices.sort_unstable();
+        indices.dedup();
+
+        View::new(SortedVecStore(
+            indices
+                .into_iter()
+                .map(|idx| {
+                    let (k, p) = &self.0[idx];
+                    (k.clone(), p)
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl<K, P> Positions for SortedVecStore<K, P>
+where
+    P: KeyPositionAsSlice,
+{
+    type Pos = P::Pos;
+
+    fn positions(&self) -> impl Iterator<Item = &'_ P::Pos> {
+        self.0.iter().flat_map(|(_, p)| p.as_position_slice())
+    }
+}
+
+/// The `Key`s are in ascending order.
+impl<K, P> Groups for SortedVecStore<K, P>
+where
+    P: KeyPositionAsSlice,
+{
+    type Key = K;
+    type Pos = P::Pos;
+
+    fn groups(&self) -> impl Iterator<Item = (&'_ K, &'_ [P::Pos])> {
+        self.0.iter().map(|(k, p)| (k, p.as_position_slice()))
+    }
+}
+
+impl<K, P> Store for SortedVecStore<K, P>
+where
+    K: Ord,
+    P: KeyPosition,
+{
+    type Key = K;
+    type Pos = P::Pos;
+
+    fn insert(&mut self, key: Self::Key, pos: Self::Pos) {
+        match self.find(&key) {
+            Ok(idx) => self.0[idx].1.add_pos(pos),
+            Err(idx) => self.0.insert(idx, (key, P::from_pos(pos))),
+        }
+    }
+
+    fn insert_many<It>(&mut self, it: It)
+    where
+        It: IntoIterator<Item = (Self::Key, Self::Pos)>,
+    {
+        let mut pairs = it.into_iter().collect::<Vec<_>>();
+        // stable sort: the order of the Positions for the same Key is kept
+        pairs.sort_by(|(l, _), (r, _)| l.cmp(r));
+
+        // the Positions of existing Keys are added directly
+        let mut entries: Vec<(K, P)> = Vec::new();
+        for (key, pos) in pairs {
+            match entries.last_mut() {
+                Some((k, p)) if *k == key => p.push_pos(pos),
+                _ => match self.find(&key) {
+                    Ok(idx) => self.0[idx].1.add_pos(pos),
+                    Err(_) => entries.push((key, P::from_pos(pos))),
+                },
+            }
+        }
+        entries.iter_mut().for_each(|(_, p)| p.normalize());
+
+        // the new Keys are sorted, the stable sort merges the two sorted runs in linear time
+        if !entries.is_empty() {
+            self.0.append(&mut entries);
+            self.0.sort_by(|(l, _), (r, _)| l.cmp(r));
+        }
+        self.0.shrink_to_fit();
+    }
+
+    /// If the `Key` is not changed, the `Store` is not touched.
+    fn update(&mut self, old_key: Self::Key, pos: Self::Pos, new_key: Self::Key) {
+        if old_key != new_key {
+            self.delete(old_key, &pos);
+            self.insert(new_key, pos);
+        }
+    }
+
+    fn delete(&mut self, key: Self::Key, pos: &Self::Pos) {
+        if let Ok(idx) = self.find(&key) {
+            if self.0[idx].1.remove_pos(pos) {
+                self.0.remove(idx);
+            }
+        }
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional)
+    }
+
+    fn clear(&mut self) {
+        self.0.clear()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, P> MergeStore for SortedVecStore<K, P>
+where
+    K: Ord,
+    P: MergeKeyPosition,
+{
+    fn merge(&mut self, other: Self) {
+        self.merge_sorted(other.0);
+    }
+}
+
+/// A proxy for exposing [`SortedVecStore`] specific extensions.
+#[repr(transparent)]
+pub struct SortedVecStoreExt<K, P>(SortedVecStore<K, P>);
+
+impl<K, P> Deref for SortedVecStore<K, P> {
+    type Target = SortedVecStoreExt<K, P>;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFTY:
+        // self is a valid pointer and
+        // SortedVecStoreExt is repr(transparent) thus has the same memory layout like SortedVecStore
+        unsafe { &*(self as *const SortedVecStore<K, P> as *const SortedVecStoreExt<K, P>) }
+    }
+}
+
+impl<K, P> SortedVecStoreExt<K, P> {
+    /// Returns all stored `Key`s in ascending order.
+    pub fn keys(&self) -> impl Iterator<Item = &'_ K> {
+        self.0 .0.iter().map(|(k, _)| k)
+    }
+
+    /// Returns smallest stored `Key`.
+    pub fn min_key(&self) -> Option<&K> {
+        self.0 .0.first().map(|(k, _)| k)
+    }
+
+    /// Returns greatest stored `Key`.
+    pub fn max_key(&self) -> Option<&K> {
+        self.0 .0.last().map(|(k, _)| k)
+    }
+
+    /// Returns all `Position`s for the `Key`s in the given `range` (in order of the `Key`s).
+    pub fn range<Q, R>(&self, range: R) -> impl Iterator<Item = &'_ P::Pos>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+        P: KeyPositionAsSlice,
+    {
+        let entries = &self.0 .0;
+
+        let start = match range.start_bound() {
+            Bound::Included(s) => entries.partition_point(|(k, _)| k.borrow() < s),
+            Bound::Excluded(s) => entries.partition_point(|(k, _)| k.borrow() <= s),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(e) => entries.partition_point(|(k, _)| k.borrow() <= e),
+            Bound::Excluded(e) => entries.partition_point(|(k, _)| k.borrow() < e),
+            Bound::Unbounded => entries.len(),
+        };
+
+        entries[start..end.max(start)]
+            .iter()
+            .flat_map(|(_, p)| p.as_position_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lookup::store::position::{MultiKeyPosition, UniqueKeyPosition};
+
+    type MultiKeySorted<K = String, X = usize> = SortedVecStore<K, MultiKeyPosition<X>>;
+
+    #[test]
+    fn store_and_lookup() {
+        let mut idx = MultiKeySorted::with_capacity(0);
+        idx.insert_many([
+            (String::from("c"), 3),
+            (String::from("a"), 1),
+            (String::from("c"), 0),
+        ]);
+        idx.insert(String::from("b"), 2);
+        idx.insert_many([(String::from("a"), 4), (String::from("d"), 5)]);
+
+        assert!(idx.key_exist("a"));
+        assert!(!idx.key_exist("z"));
+        assert_eq!(&[1, 4], idx.pos_by_key("a"));
+        assert_eq!(&[0, 3], idx.pos_by_key("c"));
+        assert_eq!(&[0usize; 0], idx.pos_by_key("z"));
+
+        assert_eq!(vec!["a", "b", "c", "d"], idx.keys().collect::<Vec<_>>());
+        assert_eq!(Some(&String::from("a")), idx.min_key());
+        assert_eq!(Some(&String::from("d")), idx.max_key());
+
+        idx.update(String::from("b"), 2, String::from("e"));
+        idx.delete(String::from("d"), &5);
+        assert_eq!(vec!["a", "c", "e"], idx.keys().collect::<Vec<_>>());
+
+        idx.clear();
+        assert_eq!(None, idx.min_key());
+    }
+
+    #[test]
+    fn range() {
+        let mut idx = SortedVecStore::<usize, UniqueKeyPosition<usize>>::with_capacity(0);
+        idx.insert_many([(5, 0), (1, 1), (3, 2), (9, 3)]);
+
+        assert_eq!(vec![&2, &0], idx.range(2..9).collect::<Vec<_>>());
+        assert_eq!(vec![&2, &0, &3], idx.range(3..).collect::<Vec<_>>());
+        assert_eq!(vec![&1, &2], idx.range(..=3).collect::<Vec<_>>());
+        assert_eq!(
+            vec![&0, &3],
+            idx.range((Bound::Excluded(3), Bound::Unbounded))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(0, idx.range(6..6).count());
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = idx.range(9..1).count();
+        assert_eq!(0, empty);
+    }
+
+    #[test]
+    fn create_view() {
+        let mut idx = MultiKeySorted::with_capacity(0);
+        idx.insert_many([
+            (String::from("a"), 0),
+            (String::from("b"), 1),
+            (String::from("c"), 2),
+            (String::from("b"), 3),
+        ]);
+
+        let view = idx.create_view([
+            String::from("c"),
+            String::from("b"),
+            String::from("c"),
+            String::from("z"),
+        ]);
+        assert!(!view.key_exist("a"));
+        assert_eq!(&[1, 3], view.pos_by_key("b"));
+        assert_eq!(vec!["b", "c"], view.keys().collect::<Vec<_>>());
+        assert_eq!(vec![&1, &3, &2], view.positions().collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn merge() {
+        let mut idx = MultiKeySorted::with_capacity(0);
+        idx.insert_many([(String::from("a"), 3), (String::from("c"), 1)]);
+
+        let mut other = MultiKeySorted::with_capacity(0);
+        other.insert_many([(String::from("b"), 2), (String::from("a"), 0)]);
+
+        idx.merge(other);
+        assert_eq!(&[0, 3], idx.pos_by_key("a"));
+        assert_eq!(&[2], idx.pos_by_key("b"));
+        assert_eq!(&[1], idx.pos_by_key("c"));
+        assert_eq!(vec!["a", "b", "c"], idx.keys().collect::<Vec<_>>());
+    }
+}
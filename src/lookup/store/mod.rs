@@ -1,8 +1,14 @@
 //! The `store` module contains the interfaces for storing and accessing the lookups.
 //!
+pub mod owned;
 pub mod position;
 
 use position::{KeyPosition, MultiKeyPosition, UniqueKeyPosition};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+    hash::Hash,
+};
 
 /// Retriever for `Key`s. This a base Trait for more retrieval implementations.
 /// Returns the positions for the searching `Key`, which the `Store` contains.
@@ -20,7 +26,7 @@ pub trait Retriever<Q> {
     /// Returns all known positions for a given iterator of `Key`s.
     ///
     /// Hint: If the input list contains a `Key` more than ones, than containts the result list
-    /// the positions also more than ones.
+    /// the positions also more than ones (see: [`Retriever::pos_by_many_keys_dedup`]).
     fn pos_by_many_keys<'a, K>(&'a self, keys: K) -> impl Iterator<Item = &'a Self::Pos>
     where
         K: IntoIterator<Item = Q>,
@@ -28,6 +34,76 @@ pub trait Retriever<Q> {
     {
         keys.into_iter().flat_map(|q| self.pos_by_key(q))
     }
+
+    /// Returns the count of the given `Key`s, which exist.
+    fn count_existing_keys<K>(&self, keys: K) -> usize
+    where
+        K: IntoIterator<Item = Q>,
+    {
+        keys.into_iter()
+            .map(|q| usize::from(self.key_exist(q)))
+            .sum()
+    }
+
+    /// Returns all given `Key`s, which not exist.
+    fn missing_keys<K>(&self, keys: K) -> impl Iterator<Item = Q>
+    where
+        K: IntoIterator<Item = Q>,
+        Q: Clone,
+    {
+        keys.into_iter().filter(|q| !self.key_exist(q.clone()))
+    }
+
+    /// Returns the count of all positions for the given `Key`s.
+    fn count_by_many_keys<K>(&self, keys: K) -> usize
+    where
+        K: IntoIterator<Item = Q>,
+    {
+        keys.into_iter().map(|q| self.pos_by_key(q).len()).sum()
+    }
+
+    /// Returns all known positions for a given iterator of `Key`s, every position only once
+    /// (in the order of the first occurrence).
+    fn pos_by_many_keys_dedup<'a, K>(&'a self, keys: K) -> impl Iterator<Item = &'a Self::Pos>
+    where
+        K: IntoIterator<Item = Q>,
+        Self::Pos: Hash + Eq + 'a,
+    {
+        let mut seen = HashSet::new();
+        self.pos_by_many_keys(keys)
+            .filter(move |pos| seen.insert(*pos))
+    }
+
+    /// Returns all known positions for a given iterator of `Key`s, every position only once
+    /// and ordered by the position.
+    ///
+    /// The sorted positions of every `Key` (e.g. `MultiKeyPosition`) are merged,
+    /// without sorting all positions.
+    fn pos_by_many_keys_sorted<'a, K>(&'a self, keys: K) -> impl Iterator<Item = &'a Self::Pos>
+    where
+        K: IntoIterator<Item = Q>,
+        Self::Pos: Ord + 'a,
+    {
+        // min-heap with the first position of every slice
+        let mut heap = keys
+            .into_iter()
+            .filter_map(|q| self.pos_by_key(q).split_first())
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+        let mut last = None;
+
+        std::iter::from_fn(move || loop {
+            let Reverse((pos, rest)) = heap.pop()?;
+            if let Some((next, rest)) = rest.split_first() {
+                heap.push(Reverse((next, rest)));
+            }
+
+            if last != Some(pos) {
+                last = Some(pos);
+                return last;
+            }
+        })
+    }
 }
 
 impl<R, Q> Retriever<Q> for &R
@@ -43,6 +119,43 @@ where
     fn pos_by_key(&self, key: Q) -> &[Self::Pos] {
         (*self).pos_by_key(key)
     }
+
+    fn count_existing_keys<K>(&self, keys: K) -> usize
+    where
+        K: IntoIterator<Item = Q>,
+    {
+        (*self).count_existing_keys(keys)
+    }
+
+    fn count_by_many_keys<K>(&self, keys: K) -> usize
+    where
+        K: IntoIterator<Item = Q>,
+    {
+        (*self).count_by_many_keys(keys)
+    }
+}
+
+impl<P> Positions for &P
+where
+    P: Positions,
+{
+    type Pos = P::Pos;
+
+    fn positions(&self) -> impl Iterator<Item = &'_ Self::Pos> {
+        (*self).positions()
+    }
+}
+
+impl<G> Groups for &G
+where
+    G: Groups,
+{
+    type Key = G::Key;
+    type Pos = G::Pos;
+
+    fn groups(&self) -> impl Iterator<Item = (&'_ Self::Key, &'_ [Self::Pos])> {
+        (*self).groups()
+    }
 }
 
 /// `Positions` create an `Iterator` for all saved positions.
@@ -53,6 +166,15 @@ pub trait Positions {
     fn positions(&self) -> impl Iterator<Item = &'_ Self::Pos>;
 }
 
+/// `Groups` create an `Iterator` for all saved `Key`s with the associated `Position`s (in one pass).
+pub trait Groups {
+    type Key;
+    type Pos;
+
+    /// Returns all `Key`s with the associated `Position`s as an iterator.
+    fn groups(&self) -> impl Iterator<Item = (&'_ Self::Key, &'_ [Self::Pos])>;
+}
+
 /// Store is an container which the mapping between the `Key`s and they `Position`s stored.
 ///
 pub trait Store {
@@ -63,7 +185,18 @@ pub trait Store {
     ///
     fn insert(&mut self, key: Self::Key, pos: Self::Pos);
 
+    /// Insert many `Key`s with the associated `Position`s in one go.
+    /// This is the fast path for (bulk) loading many `Item`s.
+    ///
+    fn insert_many<It>(&mut self, it: It)
+    where
+        It: IntoIterator<Item = (Self::Key, Self::Pos)>,
+    {
+        it.into_iter().for_each(|(key, pos)| self.insert(key, pos));
+    }
+
     /// Update means: `Key` changed, but `Position` stays the same.
+    /// Implementations can skip the `delete` and `insert`, if the `Key` is not changed.
     ///
     fn update(&mut self, old_key: Self::Key, pos: Self::Pos, new_key: Self::Key) {
         self.delete(old_key, &pos);
@@ -78,6 +211,109 @@ pub trait Store {
     /// To reduce memory allocations can create an `Store` with capacity.
     ///
     fn with_capacity(capacity: usize) -> Self;
+
+    /// Create a new empty `Store` with the same configuration (e.g. the `BuildHasher`) like this `Store`.
+    /// The default implementation is [`Store::with_capacity`].
+    ///
+    fn new_like(&self, capacity: usize) -> Self
+    where
+        Self: Sized,
+    {
+        Self::with_capacity(capacity)
+    }
+
+    /// Reserves capacity for at least `additional` more `Key`s.
+    /// The default implementation does nothing.
+    ///
+    fn reserve(&mut self, _additional: usize) {}
+
+    /// Removes all `Key`s and `Position`s.
+    /// The default implementation replaces the `Store` with a new empty `Store` (see: [`Store::new_like`]).
+    ///
+    fn clear(&mut self)
+    where
+        Self: Sized,
+    {
+        *self = self.new_like(0);
+    }
+}
+
+/// `MergeStore` is a `Store`, which can be created in parallel (feature = "rayon").
+///
+#[cfg(feature = "rayon")]
+pub trait MergeStore: Store + Sized {
+    /// Merge all `Key`s with the associated `Position`s from the `other` `Store` into this `Store`.
+    ///
+    fn merge(&mut self, other: Self);
+
+    /// Create a new `Store` for the `items` in parallel, the `Position` is the index of the `Item`.
+    /// The `items` are partitioned in chunks, for every chunk is created a `Store` (with `new_store`),
+    /// at the end all `Store`s are merged to one.
+    ///
+    fn par_new_list<N, F, I>(new_store: N, field: &F, items: &[I]) -> Self
+    where
+        N: Fn(usize) -> Self + Sync,
+        F: Fn(&I) -> Self::Key + Sync,
+        I: Sync,
+        Self: Send,
+        Self::Pos: From<usize>,
+    {
+        use rayon::prelude::*;
+
+        const MIN_CHUNK_SIZE: usize = 1_024;
+        let chunk_size = (items.len() / rayon::current_num_threads()).max(MIN_CHUNK_SIZE);
+
+        items
+            .par_chunks(chunk_size)
+            .enumerate()
+            .map(|(n, chunk)| {
+                let offset = n * chunk_size;
+                let mut store = new_store(chunk.len());
+                store.insert_many(
+                    chunk
+                        .iter()
+                        .enumerate()
+                        .map(|(pos, item)| (field(item), Self::Pos::from(offset + pos))),
+                );
+                store
+            })
+            .reduce_with(|mut store, other| {
+                store.merge(other);
+                store
+            })
+            .unwrap_or_else(|| new_store(0))
+    }
+}
+
+/// `BorrowedStore` is a `Store`, which can be modified with a borrowed `Key` (e.g. `&str` for a `String`).
+/// An owned `Key` is only created, if the `Key` does not exist in the `Store`.
+///
+pub trait BorrowedStore<Q: ?Sized>: Store {
+    /// A by [`BorrowedStore::detach`] released entry, which can be reused by [`BorrowedStore::attach`].
+    type Detached;
+
+    /// Remove the `pos` for the `key`. If it was the last `Position` for the `key`,
+    /// the entry is removed and returned, for reusing (without allocation).
+    fn detach(&mut self, key: &Q, pos: &Self::Pos) -> Option<Self::Detached>;
+
+    /// Insert the `pos` for the `key`. If the `key` does not exist,
+    /// the `detached` entry is reused or a new entry with an owned `Key` is created.
+    fn attach(&mut self, key: &Q, pos: Self::Pos, detached: Option<Self::Detached>);
+
+    /// Remove the entry for the `key` before the `Key` of the `Item` is updated,
+    /// the entry must be restored by [`BorrowedStore::restore_entry`] with the new `Key`
+    /// (also if the update of the `Item` panics).
+    /// The default implementation is [`BorrowedStore::detach`].
+    fn take_entry(&mut self, key: &Q, pos: &Self::Pos) -> Option<Self::Detached> {
+        self.detach(key, pos)
+    }
+
+    /// Restore the by [`BorrowedStore::take_entry`] removed entry for the new `key`.
+    /// Implementations can skip moving the `pos`, if the `Key` is not changed.
+    /// The default implementation is [`BorrowedStore::attach`].
+    fn restore_entry(&mut self, key: &Q, pos: Self::Pos, taken: Option<Self::Detached>) {
+        self.attach(key, pos, taken)
+    }
 }
 
 /// `Lookup` creates an unique or multi `Key` lookup.
@@ -115,6 +351,11 @@ where
         Lookup::<S, MultiKeyPosition<P::Pos>>::new()
     }
 
+    /// Create a new empty `Store` with the given capacity.
+    fn new_store(&self, capacity: usize) -> S {
+        S::with_capacity(capacity)
+    }
+
     /// Create a new `Store` for a `collection` from type `list` (e.g. `LkupVec`).
     /// The `Pos`-Type is always `usize`.
     fn new_list_store<'a, F, K, It, I: 'a>(&self, field: &F, it: It) -> S
@@ -123,12 +364,26 @@ where
         F: Fn(&I) -> K,
         S: Store<Key = K, Pos = usize>,
     {
-        let mut store = S::with_capacity(it.len());
-        it.enumerate()
-            .for_each(|(pos, item)| store.insert(field(item), pos));
+        let mut store = self.new_store(it.len());
+        store.insert_many(it.enumerate().map(|(pos, item)| (field(item), pos)));
         store
     }
 
+    /// Create a new `Store` for a `collection` from type `list` (e.g. `LkupVec`) in parallel
+    /// (see: [`MergeStore::par_new_list`]).
+    ///
+    /// The result is identical to [`Lookup::new_list_store`].
+    #[cfg(feature = "rayon")]
+    fn par_new_list_store<F, K, I>(&self, field: &F, items: &[I]) -> S
+    where
+        F: Fn(&I) -> K + Sync,
+        I: Sync,
+        S: MergeStore<Key = K, Pos = usize> + Send,
+        Self: Sync,
+    {
+        S::par_new_list(|capacity| self.new_store(capacity), field, items)
+    }
+
     /// Create a new `Store` for a `collection` from type `map` (e.g. `LkupHashMap`).
     fn new_map_store<'a, F, K, It, I: 'a>(&self, field: &F, it: It) -> S
     where
@@ -137,10 +392,41 @@ where
         S: Store<Key = K, Pos = P::Pos>,
         P::Pos: Clone + 'a,
     {
-        let mut store = S::with_capacity(it.len());
-        it.for_each(|(pos, item)| store.insert(field(item), pos.clone()));
+        let mut store = self.new_store(it.len());
+        store.insert_many(it.map(|(pos, item)| (field(item), pos.clone())));
         store
     }
+
+    /// Create a new `Store` for a `collection` from type `map` (e.g. `LkupHashMap`) in parallel.
+    /// Every thread fills an own `Store`, at the end all `Store`s are merged to one.
+    ///
+    /// The result is identical to [`Lookup::new_map_store`].
+    #[cfg(feature = "rayon")]
+    fn par_new_map_store<'a, F, K, It, I>(&self, field: &F, it: It) -> S
+    where
+        It: rayon::iter::IntoParallelIterator<Item = (&'a P::Pos, &'a I)>,
+        F: Fn(&I) -> K + Sync,
+        I: Sync + 'a,
+        S: MergeStore<Key = K, Pos = P::Pos> + Send,
+        P::Pos: Clone + Sync + 'a,
+        Self: Sync,
+    {
+        use rayon::prelude::*;
+
+        it.into_par_iter()
+            .fold(
+                || self.new_store(0),
+                |mut store, (pos, item)| {
+                    store.insert(field(item), pos.clone());
+                    store
+                },
+            )
+            .reduce_with(|mut store, other| {
+                store.merge(other);
+                store
+            })
+            .unwrap_or_else(|| self.new_store(0))
+    }
 }
 
 /// The Idea of a `View` is like database view.
@@ -178,6 +464,20 @@ where
     fn pos_by_key(&self, key: Q) -> &[Self::Pos] {
         self.0.pos_by_key(key)
     }
+
+    fn count_existing_keys<K>(&self, keys: K) -> usize
+    where
+        K: IntoIterator<Item = Q>,
+    {
+        self.0.count_existing_keys(keys)
+    }
+
+    fn count_by_many_keys<K>(&self, keys: K) -> usize
+    where
+        K: IntoIterator<Item = Q>,
+    {
+        self.0.count_by_many_keys(keys)
+    }
 }
 
 impl<P> Positions for View<P>
@@ -191,6 +491,18 @@ where
     }
 }
 
+impl<G> Groups for View<G>
+where
+    G: Groups,
+{
+    type Key = G::Key;
+    type Pos = G::Pos;
+
+    fn groups(&self) -> impl Iterator<Item = (&'_ Self::Key, &'_ [Self::Pos])> {
+        self.0.groups()
+    }
+}
+
 impl<R> std::ops::Deref for View<R>
 where
     R: std::ops::Deref,
@@ -317,4 +629,27 @@ mod tests {
         let map = MapIndex::<&str, MultiKeyPosition<usize>>::from_vec(items);
         assert_eq!(expected, map.pos_by_many_keys(keys).collect::<Vec<_>>());
     }
+
+    #[rstest]
+    #[case::empty(vec![], vec![], vec![])]
+    #[case::two_double_found(vec!["x", "x"], vec![&0, &4], vec![&0, &4])]
+    #[case::one_not_found(vec!["-"], vec![], vec![])]
+    #[case::z_a_x(vec!["z", "a", "x"], vec![&6, &1, &0, &4], vec![&0, &1, &4, &6])]
+    #[case::x_a_z_a_x(vec!["x", "a", "z", "a", "x"], vec![&0, &4, &1, &6], vec![&0, &1, &4, &6])]
+    fn iter_multi_positions_dedup(
+        #[case] keys: Vec<&str>,
+        #[case] expected_dedup: Vec<&usize>,
+        #[case] expected_sorted: Vec<&usize>,
+    ) {
+        let items = vec!["x", "a", "b", "c", "x", "y", "z"];
+        let map = MapIndex::<&str, MultiKeyPosition<usize>>::from_vec(items);
+        assert_eq!(
+            expected_dedup,
+            map.pos_by_many_keys_dedup(keys.clone()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            expected_sorted,
+            map.pos_by_many_keys_sorted(keys).collect::<Vec<_>>()
+        );
+    }
 }
@@ -0,0 +1,183 @@
+//! The `owned` module contains the [`OwnedView`], a `View` without a lifetime.
+//!
+//! A `View` created by the [`crate::lookup::store::ViewCreator`] borrows the `Store`.
+//! The [`OwnedView`] clones the `Key`s and `Position`s, so it can be stored in a struct alongside the collection,
+//! sent to another thread or kept across a mutation of the collection.
+//! After a mutation, the [`OwnedView`] can be refreshed with [`OwnedView::refresh`].
+//! The collections (e.g. [`crate::collections::list::ro::LkupList::with_owned_view`]) refuse a stale [`OwnedView`],
+//! which was not refreshed after a modification of the collection.
+//!
+use crate::lookup::store::{Groups, Positions, Retriever, Store, ViewCreator};
+use std::ops::Deref;
+
+/// An `OwnedView` is a `View` which owns the `Key`s and a copy of the `Position`s from the `Store`.
+#[derive(Debug, Clone)]
+pub struct OwnedView<K, S> {
+    keys: Vec<K>,
+    store: S,
+    // the generation of the collection, when the `View` was created or refreshed
+    pub(crate) generation: usize,
+}
+
+impl<K, S> OwnedView<K, S>
+where
+    K: Clone,
+    S: Store<Key = K>,
+    S::Pos: Clone,
+{
+    /// Create a new `OwnedView` for the given `Key`s.
+    pub fn new<'a, It>(store: &'a S, keys: It) -> Self
+    where
+        It: IntoIterator<Item = K>,
+        S: ViewCreator<'a, Key = K>,
+        S::Retriever: Groups<Key = K, Pos = S::Pos>,
+    {
+        let keys = keys.into_iter().collect::<Vec<_>>();
+        let store = Self::create_store(store, &keys);
+        Self {
+            keys,
+            store,
+            generation: 0,
+        }
+    }
+
+    /// Refresh the `Position`s for the `Key`s of this `View` from the given `Store`
+    /// (e.g. after the collection was modified).
+    /// `Key`s which not exist (anymore) in the `Store` are retained and are visible after the next `refresh`,
+    /// when they exist again.
+    pub fn refresh<'a>(&mut self, store: &'a S)
+    where
+        S: ViewCreator<'a, Key = K>,
+        S::Retriever: Groups<Key = K, Pos = S::Pos>,
+    {
+        self.store = Self::create_store(store, &self.keys);
+    }
+
+    fn create_store<'a>(store: &'a S, keys: &[K]) -> S
+    where
+        S: ViewCreator<'a, Key = K>,
+        S::Retriever: Groups<Key = K, Pos = S::Pos>,
+    {
+        let view = store.create_view(keys.iter().cloned());
+        let mut owned = store.new_like(keys.len());
+        owned.insert_many(
+            view.groups()
+                .flat_map(|(key, pos)| pos.iter().map(move |p| (key.clone(), p.clone()))),
+        );
+        owned
+    }
+}
+
+impl<K, S> OwnedView<K, S> {
+    /// The `Key`s, which this `View` was created with.
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+}
+
+impl<K, S, Q> Retriever<Q> for OwnedView<K, S>
+where
+    S: Retriever<Q>,
+{
+    type Pos = S::Pos;
+
+    fn key_exist(&self, key: Q) -> bool {
+        self.store.key_exist(key)
+    }
+
+    fn pos_by_key(&self, key: Q) -> &[Self::Pos] {
+        self.store.pos_by_key(key)
+    }
+
+    fn count_existing_keys<It>(&self, keys: It) -> usize
+    where
+        It: IntoIterator<Item = Q>,
+    {
+        self.store.count_existing_keys(keys)
+    }
+
+    fn count_by_many_keys<It>(&self, keys: It) -> usize
+    where
+        It: IntoIterator<Item = Q>,
+    {
+        self.store.count_by_many_keys(keys)
+    }
+}
+
+impl<K, S> Groups for OwnedView<K, S>
+where
+    S: Groups,
+{
+    type Key = S::Key;
+    type Pos = S::Pos;
+
+    fn groups(&self) -> impl Iterator<Item = (&'_ Self::Key, &'_ [Self::Pos])> {
+        self.store.groups()
+    }
+}
+
+impl<K, S> Positions for OwnedView<K, S>
+where
+    S: Groups,
+{
+    type Pos = S::Pos;
+
+    fn positions(&self) -> impl Iterator<Item = &'_ Self::Pos> {
+        self.store.groups().flat_map(|(_, pos)| pos)
+    }
+}
+
+impl<K, S> Deref for OwnedView<K, S>
+where
+    S: Deref,
+{
+    type Target = S::Target;
+
+    fn deref(&self) -> &Self::Target {
+        self.store.deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lookup::{hash::HashStore, index::IndexStore, store::position::MultiKeyPosition};
+
+    #[test]
+    fn hash_store() {
+        let mut idx = HashStore::<String, MultiKeyPosition<usize>>::with_capacity(0);
+        idx.insert(String::from("a"), 0);
+        idx.insert(String::from("b"), 1);
+        idx.insert(String::from("a"), 2);
+
+        let mut view = OwnedView::new(&idx, [String::from("a"), String::from("c")]);
+        assert!(view.key_exist("a"));
+        assert!(!view.key_exist("b"));
+        assert!(!view.key_exist("c"));
+        assert_eq!(&[0, 2], view.pos_by_key("a"));
+
+        // the view is not borrowing the store
+        idx.insert(String::from("c"), 3);
+        idx.delete(String::from("a"), &0);
+        assert_eq!(&[0, 2], view.pos_by_key("a"));
+
+        view.refresh(&idx);
+        assert_eq!(&[2], view.pos_by_key("a"));
+        assert_eq!(&[3], view.pos_by_key("c"));
+        assert_eq!(&[String::from("a"), String::from("c")], view.keys());
+    }
+
+    #[test]
+    fn index_store_send_to_thread() {
+        let mut idx = IndexStore::<usize, MultiKeyPosition<usize>>::with_capacity(0);
+        idx.insert(1, 0);
+        idx.insert(3, 1);
+        idx.insert(1, 2);
+
+        let view = OwnedView::new(&idx, [1, 3]);
+        let positions = std::thread::spawn(move || view.positions().copied().collect::<Vec<_>>())
+            .join()
+            .unwrap();
+        assert_eq!(vec![0, 2, 1], positions);
+    }
+}
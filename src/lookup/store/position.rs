@@ -17,6 +17,22 @@ pub trait KeyPosition {
 
     /// Remove a `pos`. If the return value is `true`, than the last position was removed.
     fn remove_pos(&mut self, pos: &Self::Pos) -> bool;
+
+    /// Add a new `pos` without checking the order and duplicates (the fast path for bulk inserts).
+    /// After adding all positions, must be called [`KeyPosition::normalize`].
+    fn push_pos(&mut self, pos: Self::Pos) {
+        self.add_pos(pos)
+    }
+
+    /// Restore the order and remove duplicates, after adding positions with [`KeyPosition::push_pos`].
+    fn normalize(&mut self) {}
+}
+
+/// Merge two `KeyPosition`s, which is necessary for creating a `Store` in parallel (see: [`crate::lookup::store::MergeStore`]).
+#[cfg(feature = "rayon")]
+pub trait MergeKeyPosition: KeyPosition {
+    /// Add all positions from the `other` `KeyPosition`.
+    fn merge(&mut self, other: Self);
 }
 
 /// Convert the all position from `KeyPosition` into a Slice.
@@ -41,7 +57,8 @@ where
 /// `UniqueKeyPosition` is an optional container for none or maximal one `Key` position.
 ///
 /// ## Panics
-/// Panics, the Posion must be unique, so you can not add a further `pos` ([UniqueKeyPosition::add_pos]) .
+/// Panics, the Posion must be unique, so you can not add a further `pos` ([UniqueKeyPosition::add_pos]),
+/// if there is already a `pos`.
 ///
 pub type UniqueKeyPosition<P> = Option<P>;
 
@@ -56,11 +73,15 @@ where
         Some(pos)
     }
 
+    /// If it is None (e.g. the last `pos` was removed), than the `pos` is set.
+    ///
     /// ## Panics
     /// Panics, the Posion must be unique, so you can not add a further `pos`.
-    fn add_pos(&mut self, _pos: P) {
-        // maybe reuse this key, if the value is None, so can add_pos set a new Some value
-        panic!("unique UniqueKeyPositon can not add a new position")
+    fn add_pos(&mut self, pos: P) {
+        match self {
+            None => *self = Some(pos),
+            Some(_) => panic!("unique UniqueKeyPositon can not add a new position"),
+        }
     }
 
     /// If it is Some, than remove the `pos` and set the value to Nome.
@@ -77,6 +98,22 @@ where
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<P> MergeKeyPosition for UniqueKeyPosition<P>
+where
+    P: PartialEq,
+{
+    /// ## Panics
+    /// Panics, if both contains a `pos` (the `pos` must be unique).
+    fn merge(&mut self, other: Self) {
+        match (self.as_ref(), other) {
+            (_, None) => {}
+            (None, other) => *self = other,
+            (Some(_), Some(pos)) => self.add_pos(pos),
+        }
+    }
+}
+
 impl<P> KeyPositionAsSlice for UniqueKeyPosition<P> {
     type Pos = P;
 
@@ -110,9 +147,38 @@ where
 
     /// Remove one Position and return left free Indices.
     fn remove_pos(&mut self, pos: &P) -> bool {
-        self.retain(|v| v != pos);
+        if let Ok(idx) = self.binary_search(pos) {
+            self.remove(idx);
+        }
         self.is_empty()
     }
+
+    /// Append the Position, the order is restored by [`KeyPosition::normalize`].
+    fn push_pos(&mut self, pos: P) {
+        self.push(pos);
+    }
+
+    /// Sort all Positions and remove duplicates in one go.
+    fn normalize(&mut self) {
+        self.sort_unstable();
+        self.dedup();
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<P> MergeKeyPosition for MultiKeyPosition<P>
+where
+    P: Ord + PartialEq,
+{
+    /// Append all Positions from the (normalized) `other` and restore the order.
+    /// If all Positions from `other` are greater (e.g. appended `Item`s), no sort is necessary.
+    fn merge(&mut self, mut other: Self) {
+        let sorted = matches!((self.last(), other.first()), (Some(l), Some(f)) if l < f);
+        self.append(&mut other);
+        if !sorted {
+            self.normalize();
+        }
+    }
 }
 
 impl<P> KeyPositionAsSlice for MultiKeyPosition<P> {
@@ -141,6 +207,15 @@ mod tests {
             UniqueKeyPosition::from_pos(1).add_pos(2);
         }
 
+        #[test]
+        fn reuse_after_remove() {
+            let mut x = UniqueKeyPosition::from_pos(1);
+            assert!(x.remove_pos(&1));
+
+            x.add_pos(2);
+            assert_eq!(x.as_position_slice(), &[2]);
+        }
+
         #[test]
         fn as_position() {
             let mut x = UniqueKeyPosition::from_pos(1);
@@ -151,6 +226,27 @@ mod tests {
             assert_eq!(x.as_position_slice(), &[]);
         }
 
+        #[cfg(feature = "rayon")]
+        #[test]
+        fn merge() {
+            let mut x = UniqueKeyPosition::default();
+            x.merge(None);
+            assert_eq!(x.as_position_slice(), &[]);
+
+            x.merge(Some(1));
+            assert_eq!(x.as_position_slice(), &[1]);
+
+            x.merge(None);
+            assert_eq!(x.as_position_slice(), &[1]);
+        }
+
+        #[cfg(feature = "rayon")]
+        #[test]
+        #[should_panic]
+        fn merge_with_panic() {
+            UniqueKeyPosition::from_pos(1).merge(Some(2));
+        }
+
         #[test]
         fn remove_pos() {
             let mut x = UniqueKeyPosition::from_pos(1);
@@ -231,5 +327,30 @@ mod tests {
             // remove after last
             assert!(m.remove_pos(&3));
         }
+
+        #[cfg(feature = "rayon")]
+        #[test]
+        fn merge() {
+            let mut m = MultiKeyPosition::from_pos(5);
+            m.add_pos(1);
+            m.merge(vec![0, 5, 3]);
+            assert_eq!(&[0, 1, 3, 5], m.as_slice());
+
+            // all Positions are greater
+            m.merge(vec![6, 8]);
+            assert_eq!(&[0, 1, 3, 5, 6, 8], m.as_slice());
+        }
+
+        #[test]
+        fn push_and_normalize() {
+            let mut m = MultiKeyPosition::from_pos(5);
+            m.push_pos(3);
+            m.push_pos(5);
+            m.push_pos(1);
+            assert_eq!(&[5, 3, 5, 1], m.as_slice());
+
+            m.normalize();
+            assert_eq!(&[1, 3, 5], m.as_slice());
+        }
     }
 }